@@ -0,0 +1,57 @@
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Part};
+use std::collections::BTreeMap;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+// Expected answers for a day, keyed by input file name. The file is a small
+// subset of TOML with one table per input file:
+//
+//     ["input.txt"]
+//     part1 = 1141
+//     part2 = 6634
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    inputs: BTreeMap<String, BTreeMap<Part, u64>>
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut answers = Answers::default();
+        let mut current: Option<String> = None;
+        for line in parse::lines(text) {
+            let content = line.text.split('#').next().unwrap_or("").trim();
+            if content.is_empty() {
+                continue;
+            }
+            if let Some(header) = content.strip_prefix('[') {
+                let Some(header) = header.strip_suffix(']') else { return Err(line.error_at_end("']'")); };
+                let Some(file) = header.strip_prefix('"').and_then(|h| h.strip_suffix('"')) else {
+                    return Err(line.error(header, "a quoted input file name"));
+                };
+                answers.inputs.entry(file.to_string()).or_default();
+                current = Some(file.to_string());
+                continue;
+            }
+            let Some(file) = &current else { return Err(line.error(content, "a table header such as '[\"input.txt\"]'")); };
+            let Some((key, value)) = content.split_once('=') else { return Err(line.error_at_end("'='")); };
+            let key = key.trim();
+            let part = match key {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => return Err(line.error(key, "'part1' or 'part2'"))
+            };
+            let value = line.parse::<u64>(value.trim(), "an integer answer")?;
+            answers.inputs.entry(file.clone()).or_default().insert(part, value);
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, file: &str, part: Part) -> Option<Answer> {
+        self.inputs.get(file)?.get(&part).map(|&value| Answer::Number(value))
+    }
+
+    pub fn files(&self) -> impl Iterator<Item=&str> {
+        self.inputs.keys().map(String::as_str)
+    }
+}
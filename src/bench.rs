@@ -0,0 +1,165 @@
+use crate::parse::{self, ParseError};
+use crate::solution::{DynSolution, Part};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    Parse,
+    Solve(Part)
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Phase::Parse => "parse".to_string(),
+            Phase::Solve(part) => format!("part{}", part)
+        };
+        f.pad(&name)
+    }
+}
+
+impl Phase {
+    fn from_name(name: &str) -> Option<Phase> {
+        match name {
+            "parse" => Some(Phase::Parse),
+            "part1" => Some(Phase::Solve(Part::One)),
+            "part2" => Some(Phase::Solve(Part::Two)),
+            _ => None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        Stats { min: samples[0], median, max: samples[n - 1] }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub day: u32,
+    pub phase: Phase,
+    pub stats: Stats
+}
+
+// Times parsing and each requested part separately, repeating the whole day
+// `runs` times. Every run parses afresh so that parse timings are sampled as
+// often as the parts.
+pub fn bench_day(solution: &dyn DynSolution, input: &str, example: bool, parts: &[Part], runs: usize) -> Result<Vec<Timing>, ParseError> {
+    assert!(runs > 0, "at least one run is required");
+    let mut samples: Vec<(Phase, Vec<Duration>)> = Vec::new();
+    samples.push((Phase::Parse, Vec::with_capacity(runs)));
+    samples.extend(parts.iter().map(|&part| (Phase::Solve(part), Vec::with_capacity(runs))));
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = solution.parse_input(input, example)?;
+        samples[0].1.push(start.elapsed());
+        for (i, &part) in parts.iter().enumerate() {
+            let start = Instant::now();
+            std::hint::black_box(solution.solve(parsed.as_ref(), part));
+            samples[i + 1].1.push(start.elapsed());
+        }
+    }
+    Ok(samples.into_iter().map(|(phase, samples)| {
+        Timing { day: solution.day(), phase, stats: Stats::from_samples(samples) }
+    }).collect())
+}
+
+// Baseline files hold one timing per line as nanoseconds:
+//
+//     # day phase min median max
+//     1 parse 10250 12100 20042
+pub fn format_baseline(timings: &[Timing]) -> String {
+    let mut text = "# day phase min median max (nanoseconds)\n".to_string();
+    for timing in timings {
+        let Stats { min, median, max } = timing.stats;
+        text += &format!("{} {} {} {} {}\n", timing.day, timing.phase, min.as_nanos(), median.as_nanos(), max.as_nanos());
+    }
+    text
+}
+
+pub fn parse_baseline(text: &str) -> Result<Vec<Timing>, ParseError> {
+    parse::lines(text).filter(|line| {
+        let content = line.text.trim();
+        !content.is_empty() && !content.starts_with('#')
+    }).map(|line| {
+        let fields: Vec<&str> = line.text.split_whitespace().collect();
+        let [day, phase, min, median, max] = fields[..] else { return Err(line.error(line.text, "'day phase min median max'")); };
+        let day = line.parse::<u32>(day, "a day")?;
+        let phase = Phase::from_name(phase).ok_or_else(|| line.error(phase, "'parse', 'part1' or 'part2'"))?;
+        let nanos = |field| line.parse::<u64>(field, "a duration in nanoseconds").map(Duration::from_nanos);
+        Ok(Timing { day, phase, stats: Stats { min: nanos(min)?, median: nanos(median)?, max: nanos(max)? } })
+    }).collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub baseline: Duration,
+    // Relative change in the median, e.g. 0.25 for 25% slower.
+    pub change: f64,
+    pub regression: bool
+}
+
+// Compares medians against the baseline entry for the same day and phase.
+// A change beyond `threshold` (a fraction, e.g. 0.2) counts as a regression.
+pub fn compare(timing: &Timing, baseline: &[Timing], threshold: f64) -> Option<Comparison> {
+    let base = baseline.iter().find(|base| base.day == timing.day && base.phase == timing.phase)?;
+    let base_median = base.stats.median.as_secs_f64();
+    let change = if base_median > 0.0 {
+        timing.stats.median.as_secs_f64() / base_median - 1.0
+    } else {
+        0.0
+    };
+    Some(Comparison { baseline: base.stats.median, change, regression: change > threshold })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_median() {
+        let ms = Duration::from_millis;
+        assert_eq!(Stats::from_samples(vec![ms(3), ms(1), ms(2)]), Stats { min: ms(1), median: ms(2), max: ms(3) });
+        assert_eq!(Stats::from_samples(vec![ms(4), ms(1), ms(2), ms(3)]).median, Duration::from_micros(2500));
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let timings = vec![
+            Timing { day: 1, phase: Phase::Parse, stats: Stats::from_samples(vec![Duration::from_nanos(10)]) },
+            Timing { day: 12, phase: Phase::Solve(Part::Two), stats: Stats::from_samples(vec![Duration::from_nanos(5), Duration::from_nanos(7)]) },
+        ];
+        assert_eq!(parse_baseline(&format_baseline(&timings)).unwrap(), timings);
+    }
+
+    #[test]
+    fn baseline_errors_are_located() {
+        let err = parse_baseline("1 parse 1 2 3\n2 part3 1 2 3\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn compare_flags_regressions() {
+        let timing = |median| Timing { day: 1, phase: Phase::Parse, stats: Stats::from_samples(vec![Duration::from_micros(median)]) };
+        let baseline = vec![timing(100)];
+        assert!(compare(&timing(130), &baseline, 0.2).unwrap().regression);
+        assert!(!compare(&timing(110), &baseline, 0.2).unwrap().regression);
+        assert!(compare(&Timing { day: 2, ..timing(100) }, &baseline, 0.2).is_none());
+    }
+}
@@ -0,0 +1,311 @@
+use aoc2025::bench::{bench_day, compare, format_baseline, parse_baseline, Timing};
+use aoc2025::days;
+use aoc2025::generator::{generate, GenOptions};
+use aoc2025::input::InputOptions;
+use aoc2025::output::{format_results, OutputFormat};
+use aoc2025::pool::{run_jobs, Job, Outcome};
+use aoc2025::solution::{DynSolution, Part};
+use aoc2025::util::format_duration;
+use aoc2025::verify::{verify_day, Status};
+use std::env;
+use std::fs;
+use std::process::exit;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const USAGE: &str = "usage: aoc run <day|all> [--part <1|2>] [--output <text|json|csv>]
+                      [--jobs N] [--timeout SECS] [--example[=N]] [PATH|-]
+       aoc verify [day|all] [--part <1|2>]
+       aoc bench <day|all> [--part <1|2>] [--runs N] [--save FILE]
+                 [--compare FILE] [--threshold PCT] [--example[=N]]
+       aoc gen <day> [--seed N] [--size N]
+
+Inputs are read from $AOC_INPUT_DIR/<day>/input.txt, falling back to
+./inputs/<day> and the crate's inputs directory. --example selects
+test_input.txt (or test_inputN.txt) instead, and solves it with the
+example's parameters where they differ, e.g. Day 8's connection count.
+PATH, or - for stdin, overrides the search when running a single day. --output json or csv
+prints the day, part, input file, answer and solve time of every part.
+Each part runs as a separate job on --jobs worker threads (default: one
+per CPU); results are still printed in order. Jobs running longer than
+--timeout seconds are reported as timed out; their worker stays busy
+until the job ends, so it doesn't run anything else.
+
+verify runs every input file of each day and checks the results against
+inputs/<day>/answers.toml, exiting non-zero on any mismatch.
+
+bench times parsing and each part separately over --runs runs (default 10)
+and reports the min/median/max. --save writes the timings to a baseline
+file and --compare flags medians more than --threshold percent (default 20)
+slower than the baseline, exiting non-zero on any regression.
+
+gen prints a random input for a day. The same --seed always gives the same
+input; without one a seed is picked and printed to stderr. --size scales
+the input (default 100), e.g. the number of lines or machines.";
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    exit(2);
+}
+
+fn parse_days(arg: &str) -> Vec<&'static dyn DynSolution> {
+    if arg == "all" {
+        return days::ALL.to_vec();
+    }
+    let Ok(day) = arg.parse::<u32>() else { usage_error(&format!("Invalid day: {}", arg)); };
+    let Some(solution) = days::get(day) else { usage_error(&format!("No solution for day {}", day)); };
+    vec![solution]
+}
+
+fn parse_part(arg: Option<String>) -> Part {
+    let Some(arg) = arg else { usage_error("Missing value for --part"); };
+    arg.parse::<u32>().ok().and_then(Part::from_number).unwrap_or_else(|| {
+        usage_error(&format!("Invalid part: {}", arg))
+    })
+}
+
+fn run(mut args: impl Iterator<Item=String>) {
+    let Some(day_arg) = args.next() else { usage_error("Missing day"); };
+    let solutions = parse_days(&day_arg);
+    let mut parts = Part::ALL.to_vec();
+    let mut options = InputOptions::default();
+    let mut format = OutputFormat::Text;
+    let mut workers = thread::available_parallelism().map_or(1, |n| n.get());
+    let mut timeout: Option<Duration> = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => parts = vec![parse_part(args.next())],
+            "--jobs" | "-j" => workers = parse_value::<usize>(&arg, args.next()).max(1),
+            "--timeout" => {
+                let secs = parse_value::<f64>(&arg, args.next());
+                timeout = Some(Duration::try_from_secs_f64(secs).unwrap_or_else(|_| usage_error(&format!("Invalid timeout: {}", secs))));
+            }
+            "--output" | "-o" => {
+                let value: String = parse_value(&arg, args.next());
+                format = value.parse().unwrap_or_else(|message: String| usage_error(&message));
+            }
+            _ => options.apply_arg(&arg).unwrap_or_else(|message| usage_error(&message))
+        }
+    }
+    if options.path.is_some() && solutions.len() > 1 {
+        usage_error("An input path can only be given when running a single day");
+    }
+    let num_days = solutions.len();
+    let mut jobs = Vec::new();
+    let mut unreadable = 0;
+    for solution in solutions {
+        match options.read(solution.day()) {
+            Ok((source, input)) => {
+                let input_name: Arc<str> = Arc::from(source.to_string());
+                let input: Arc<str> = Arc::from(input);
+                jobs.extend(parts.iter().map(|&part| {
+                    Job { solution, part, input_name: input_name.clone(), input: input.clone(), example: options.example.is_some() }
+                }));
+            }
+            Err(err) => {
+                eprintln!("{}", err);
+                unreadable += 1;
+            }
+        }
+    }
+    let outcomes = run_jobs(&jobs, workers, timeout, |job, outcome| match outcome {
+        // Text output is streamed so that slow days don't hold up the rest.
+        Outcome::Finished(result) if format == OutputFormat::Text => {
+            print!("{}", format_results(std::slice::from_ref(result), format));
+        }
+        Outcome::Finished(_) => {}
+        Outcome::Failed(message) => eprintln!("Day {} part {}: {}", job.solution.day(), job.part, message),
+        Outcome::TimedOut(timeout) => {
+            eprintln!("Day {} part {}: timed out after {}", job.solution.day(), job.part, format_duration(*timeout));
+        }
+    });
+    let results: Vec<_> = outcomes.iter().filter_map(|outcome| match outcome {
+        Outcome::Finished(result) => Some(result.clone()),
+        _ => None
+    }).collect();
+    if format != OutputFormat::Text {
+        print!("{}", format_results(&results, format));
+    }
+    let jobs_where = |predicate: fn(&Outcome) -> bool| -> Vec<&Job> {
+        jobs.iter().zip(&outcomes).filter(|(_, outcome)| predicate(outcome)).map(|(job, _)| job).collect()
+    };
+    let failed = jobs_where(|outcome| matches!(outcome, Outcome::Failed(_)));
+    let timed_out = jobs_where(|outcome| matches!(outcome, Outcome::TimedOut(_)));
+    if num_days > 1 || !failed.is_empty() || !timed_out.is_empty() {
+        eprintln!("{} jobs: {} finished, {} failed, {} timed out", jobs.len(), results.len(), failed.len(), timed_out.len());
+        for (label, jobs) in [("failed", &failed), ("timed out", &timed_out)] {
+            for job in jobs.iter() {
+                eprintln!("  {}: day {} part {} ({})", label, job.solution.day(), job.part, job.input_name);
+            }
+        }
+    }
+    if unreadable > 0 || !failed.is_empty() || !timed_out.is_empty() {
+        exit(1);
+    }
+}
+
+fn verify(mut args: impl Iterator<Item=String>) {
+    let mut solutions = days::ALL.to_vec();
+    let mut parts = Part::ALL.to_vec();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => parts = vec![parse_part(args.next())],
+            _ if !arg.starts_with('-') => solutions = parse_days(&arg),
+            _ => usage_error(&format!("Unknown argument: {}", arg))
+        }
+    }
+    let (mut passed, mut failed, mut missing, mut errors) = (0, 0, 0, 0);
+    for solution in solutions {
+        let checks = match verify_day(solution, &parts) {
+            Ok(checks) => checks,
+            Err(err) => {
+                eprintln!("Day {}: {}", solution.day(), err);
+                errors += 1;
+                continue;
+            }
+        };
+        for check in checks {
+            let result = match &check.status {
+                Status::Pass => {
+                    passed += 1;
+                    format!("PASS     {}", check.answer)
+                }
+                Status::Fail { expected } => {
+                    failed += 1;
+                    format!("FAIL     {} (expected {})", check.answer, expected)
+                }
+                Status::Missing => {
+                    missing += 1;
+                    format!("MISSING  {}", check.answer)
+                }
+            };
+            println!("Day {:>2} part {} {:<16} {:>10}  {}", check.day, check.part, check.file, format_duration(check.elapsed), result);
+        }
+    }
+    println!("{} passed, {} failed, {} missing, {} errors", passed, failed, missing, errors);
+    if failed > 0 || errors > 0 {
+        exit(1);
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, arg: Option<String>) -> T {
+    let Some(arg) = arg else { usage_error(&format!("Missing value for {}", flag)); };
+    arg.parse::<T>().unwrap_or_else(|_| usage_error(&format!("Invalid value for {}: {}", flag, arg)))
+}
+
+fn bench(mut args: impl Iterator<Item=String>) {
+    let Some(day_arg) = args.next() else { usage_error("Missing day"); };
+    let solutions = parse_days(&day_arg);
+    let mut parts = Part::ALL.to_vec();
+    let mut options = InputOptions::default();
+    let mut runs = 10;
+    let mut save: Option<String> = None;
+    let mut baseline: Option<Vec<Timing>> = None;
+    let mut threshold = 20.0;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => parts = vec![parse_part(args.next())],
+            "--runs" => runs = parse_value::<usize>(&arg, args.next()).max(1),
+            "--save" => save = Some(parse_value(&arg, args.next())),
+            "--threshold" => threshold = parse_value::<f64>(&arg, args.next()),
+            "--compare" => {
+                let path: String = parse_value(&arg, args.next());
+                let text = fs::read_to_string(&path).unwrap_or_else(|err| {
+                    eprintln!("Failed to read {}: {}", path, err);
+                    exit(1);
+                });
+                baseline = Some(parse_baseline(&text).unwrap_or_else(|err| {
+                    eprintln!("{}", err.in_file(path));
+                    exit(1);
+                }));
+            }
+            _ => options.apply_arg(&arg).unwrap_or_else(|message| usage_error(&message))
+        }
+    }
+    if options.path.is_some() && solutions.len() > 1 {
+        usage_error("An input path can only be given when running a single day");
+    }
+    let mut timings = Vec::new();
+    let mut failed = false;
+    let mut regressions = 0;
+    for solution in solutions {
+        let result = options.read(solution.day()).map_err(|err| err.to_string()).and_then(|(source, input)| {
+            bench_day(solution, &input, options.example.is_some(), &parts, runs).map_err(|err| err.in_file(source.to_string()).to_string())
+        });
+        let day_timings = match result {
+            Ok(day_timings) => day_timings,
+            Err(err) => {
+                eprintln!("{}", err);
+                failed = true;
+                continue;
+            }
+        };
+        for timing in &day_timings {
+            let stats = &timing.stats;
+            let mut line = format!("Day {:>2} {:<6} min {:>10}  median {:>10}  max {:>10}", timing.day, timing.phase,
+                format_duration(stats.min), format_duration(stats.median), format_duration(stats.max));
+            if let Some(baseline) = &baseline {
+                match compare(timing, baseline, threshold / 100.0) {
+                    Some(comparison) => {
+                        line += &format!("  baseline {:>10} {:>+7.1}%", format_duration(comparison.baseline), comparison.change * 100.0);
+                        if comparison.regression {
+                            line += "  REGRESSION";
+                            regressions += 1;
+                        }
+                    }
+                    None => line += "  no baseline"
+                }
+            }
+            println!("{}", line);
+        }
+        timings.extend(day_timings);
+    }
+    if let Some(path) = save {
+        if let Err(err) = fs::write(&path, format_baseline(&timings)) {
+            eprintln!("Failed to write {}: {}", path, err);
+            exit(1);
+        }
+        println!("Saved baseline to {}", path);
+    }
+    if baseline.is_some() {
+        println!("{} regressions", regressions);
+    }
+    if failed || regressions > 0 {
+        exit(1);
+    }
+}
+
+fn generate_input(mut args: impl Iterator<Item=String>) {
+    let Some(day_arg) = args.next() else { usage_error("Missing day"); };
+    let Ok(day) = day_arg.parse::<u32>() else { usage_error(&format!("Invalid day: {}", day_arg)); };
+    let mut seed: Option<u64> = None;
+    let mut size = 100;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => seed = Some(parse_value(&arg, args.next())),
+            "--size" => size = parse_value::<usize>(&arg, args.next()).max(1),
+            _ => usage_error(&format!("Unknown argument: {}", arg))
+        }
+    }
+    let seed = seed.unwrap_or_else(|| {
+        let seed = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_nanos() as u64);
+        eprintln!("seed: {}", seed);
+        seed
+    });
+    let Some(input) = generate(day, &GenOptions { seed, size }) else { usage_error(&format!("No generator for day {}", day)); };
+    print!("{}", input);
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        Some("run") => run(args),
+        Some("verify") => verify(args),
+        Some("bench") => bench(args),
+        Some("gen") => generate_input(args),
+        Some(command) => usage_error(&format!("Unknown command: {}", command)),
+        None => usage_error("Missing command")
+    }
+}
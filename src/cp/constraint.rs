@@ -0,0 +1,349 @@
+use super::domain::{Domain, PropagateResult};
+
+// A relation between variables, enforced by narrowing their domains.
+// `propagate` must never remove a value that is part of a solution, and once
+// every variable it mentions is fixed it must report Unsat unless the
+// relation holds.
+pub trait Constraint {
+    fn propagate(&self, domains: &mut [Domain]) -> PropagateResult;
+
+    // Every variable `propagate` reads or narrows. The constraint is only
+    // propagated again after one of them changes.
+    fn vars(&self) -> Vec<usize>;
+}
+
+// The variables in `vars` add up to `dst`.
+pub struct SumConstraint {
+    pub vars: Vec::<usize>,
+    pub dst: usize
+}
+
+impl Constraint for SumConstraint {
+    fn propagate(&self, domains: &mut [Domain]) -> PropagateResult {
+        // Sums are taken in i64 so that many large domains can't overflow.
+        let sum_low: i64 = self.vars.iter().map(|&v| domains[v].low as i64).sum();
+        let sum_high: i64 = self.vars.iter().map(|&v| domains[v].high as i64).sum();
+        let result = update_low(&mut domains[self.dst], sum_low);
+        let result = result | update_high(&mut domains[self.dst], sum_high);
+        if result == PropagateResult::Unsat {
+            return result;
+        }
+        let dst_low = domains[self.dst].low as i64;
+        let dst_high = domains[self.dst].high as i64;
+
+        self.vars.iter().fold(result, |result, &v| {
+            let new_low = dst_low - sum_high + domains[v].high as i64;
+            let new_high = dst_high - sum_low + domains[v].low as i64;
+            result | update_low(&mut domains[v], new_low) | update_high(&mut domains[v], new_high)
+        })
+    }
+
+    fn vars(&self) -> Vec<usize> {
+        self.vars.iter().copied().chain([self.dst]).collect()
+    }
+}
+
+// How the two sides of a LinearConstraint compare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    Eq,
+    Ne,
+    Le,
+    Ge
+}
+
+impl Relation {
+    // Whether `lhs relation rhs`.
+    pub fn holds(self, lhs: i64, rhs: i64) -> bool {
+        match self {
+            Relation::Eq => lhs == rhs,
+            Relation::Ne => lhs != rhs,
+            Relation::Le => lhs <= rhs,
+            Relation::Ge => lhs >= rhs
+        }
+    }
+}
+
+// `sum(coefficient * var) relation rhs` over the `terms`.
+#[derive(Debug, Clone)]
+pub struct LinearConstraint {
+    pub terms: Vec<(i32, usize)>,
+    pub relation: Relation,
+    // Wider than the variables so that negating a strict bound can't
+    // overflow.
+    pub rhs: i64
+}
+
+// a / b rounded towards negative and positive infinity.
+fn div_floor(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) { q - 1 } else { q }
+}
+
+fn div_ceil(a: i64, b: i64) -> i64 {
+    -div_floor(-a, b)
+}
+
+// Domain updates from bounds that may not fit in an i32; anything beyond the
+// i32 range is past the other bound or no tighter than the current one.
+fn update_low(domain: &mut Domain, value: i64) -> PropagateResult {
+    if value > domain.high as i64 { PropagateResult::Unsat } else { domain.update_low(value.max(i32::MIN as i64) as i32) }
+}
+
+fn update_high(domain: &mut Domain, value: i64) -> PropagateResult {
+    if value < domain.low as i64 { PropagateResult::Unsat } else { domain.update_high(value.min(i32::MAX as i64) as i32) }
+}
+
+impl LinearConstraint {
+    // The smallest and largest values of a term over its domain.
+    fn term_bounds(coefficient: i64, domain: Domain) -> (i64, i64) {
+        let (a, b) = (coefficient * domain.low as i64, coefficient * domain.high as i64);
+        (a.min(b), a.max(b))
+    }
+
+    // The smallest and largest values the left hand side can take.
+    fn bounds(&self, domains: &[Domain]) -> (i64, i64) {
+        self.terms.iter().fold((0, 0), |(low, high), &(a, v)| {
+            let (term_low, term_high) = Self::term_bounds(a as i64, domains[v]);
+            (low + term_low, high + term_high)
+        })
+    }
+
+    // Whether the current domains force the relation to hold or to fail,
+    // or None if it could still go either way.
+    pub fn entailed(&self, domains: &[Domain]) -> Option<bool> {
+        let (low, high) = self.bounds(domains);
+        let rhs = self.rhs;
+        if low == high {
+            return Some(self.relation.holds(low, rhs));
+        }
+        match self.relation {
+            Relation::Le if high <= rhs => Some(true),
+            Relation::Le if low > rhs => Some(false),
+            Relation::Ge if low >= rhs => Some(true),
+            Relation::Ge if high < rhs => Some(false),
+            Relation::Eq | Relation::Ne if rhs < low || rhs > high => Some(self.relation == Relation::Ne),
+            _ => None
+        }
+    }
+
+    // The constraint that holds exactly when this one doesn't.
+    pub fn negation(&self) -> LinearConstraint {
+        let (relation, rhs) = match self.relation {
+            Relation::Eq => (Relation::Ne, Some(self.rhs)),
+            Relation::Ne => (Relation::Eq, Some(self.rhs)),
+            Relation::Le => (Relation::Ge, self.rhs.checked_add(1)),
+            Relation::Ge => (Relation::Le, self.rhs.checked_sub(1))
+        };
+        match rhs {
+            Some(rhs) => LinearConstraint { terms: self.terms.clone(), relation, rhs },
+            // The left hand side is an i64, so it is always at most i64::MAX
+            // and at least i64::MIN. The negation never holds, like 0 != 0.
+            None => LinearConstraint { terms: Vec::new(), relation: Relation::Ne, rhs: 0 }
+        }
+    }
+
+    // Narrows every term so the left hand side can be at most `rhs`, or at
+    // least it if `sign` is -1.
+    fn propagate_le(&self, domains: &mut [Domain], sign: i64) -> PropagateResult {
+        let rhs = sign * self.rhs;
+        let min_sum: i64 = self.terms.iter().map(|&(a, v)| Self::term_bounds(sign * a as i64, domains[v]).0).sum();
+        if min_sum > rhs {
+            return PropagateResult::Unsat;
+        }
+        self.terms.iter().fold(PropagateResult::NoChange, |result, &(a, v)| {
+            if result == PropagateResult::Unsat {
+                return result;
+            }
+            let a = sign * a as i64;
+            // a * x can be at most what the other terms leave over.
+            let slack = rhs - (min_sum - Self::term_bounds(a, domains[v]).0);
+            result | match a.signum() {
+                1 => update_high(&mut domains[v], div_floor(slack, a)),
+                -1 => update_low(&mut domains[v], div_ceil(slack, a)),
+                _ => PropagateResult::NoChange
+            }
+        })
+    }
+
+    // Bounds can't express a hole, so a forbidden value only narrows a domain
+    // when it is the one unfixed variable's low or high bound.
+    fn propagate_ne(&self, domains: &mut [Domain]) -> PropagateResult {
+        let mut unfixed = self.terms.iter().filter(|&&(a, v)| a != 0 && !domains[v].is_fixed());
+        let (Some(&(a, v)), None) = (unfixed.next(), unfixed.next()) else {
+            return if self.entailed(domains) == Some(false) { PropagateResult::Unsat } else { PropagateResult::NoChange };
+        };
+        let rest: i64 = self.terms.iter().filter(|&&(_, u)| u != v).map(|&(b, u)| b as i64 * domains[u].low as i64).sum();
+        let remainder = self.rhs - rest;
+        if remainder % a as i64 != 0 {
+            return PropagateResult::NoChange;
+        }
+        let forbidden = remainder / a as i64;
+        if forbidden == domains[v].low as i64 {
+            update_low(&mut domains[v], forbidden + 1)
+        } else if forbidden == domains[v].high as i64 {
+            update_high(&mut domains[v], forbidden - 1)
+        } else {
+            PropagateResult::NoChange
+        }
+    }
+}
+
+impl Constraint for LinearConstraint {
+    fn propagate(&self, domains: &mut [Domain]) -> PropagateResult {
+        match self.relation {
+            Relation::Le => self.propagate_le(domains, 1),
+            Relation::Ge => self.propagate_le(domains, -1),
+            Relation::Eq => self.propagate_le(domains, 1) | self.propagate_le(domains, -1),
+            Relation::Ne => self.propagate_ne(domains)
+        }
+    }
+
+    fn vars(&self) -> Vec<usize> {
+        self.terms.iter().map(|&(_, v)| v).collect()
+    }
+}
+
+// No two of the variables in `vars` take the same value.
+#[derive(Debug, Clone)]
+pub struct AllDifferentConstraint {
+    pub vars: Vec<usize>
+}
+
+impl Constraint for AllDifferentConstraint {
+    // Looks for Hall intervals: ranges of values with exactly as many
+    // variables confined to them as there are values. Those variables use up
+    // the whole range, so every other variable's bounds are pushed out of it,
+    // and a range with too many variables is unsatisfiable. Trying every pair
+    // of bounds is cubic, but the variable counts here are small.
+    fn propagate(&self, domains: &mut [Domain]) -> PropagateResult {
+        let mut result = PropagateResult::NoChange;
+        let lows: Vec<i32> = self.vars.iter().map(|&v| domains[v].low).collect();
+        let highs: Vec<i32> = self.vars.iter().map(|&v| domains[v].high).collect();
+        for &low in &lows {
+            for &high in highs.iter().filter(|&&high| high >= low) {
+                let inside = self.vars.iter().filter(|&&v| domains[v].low >= low && domains[v].high <= high).count();
+                let size = high as i64 - low as i64 + 1;
+                if inside as i64 > size {
+                    return PropagateResult::Unsat;
+                }
+                if inside as i64 == size {
+                    for &v in &self.vars {
+                        let domain = &mut domains[v];
+                        if domain.low >= low && domain.high <= high {
+                            continue;
+                        }
+                        if (low..=high).contains(&domain.low) {
+                            result = result | update_low(domain, high as i64 + 1);
+                        }
+                        if (low..=high).contains(&domain.high) {
+                            result = result | update_high(domain, low as i64 - 1);
+                        }
+                        if result == PropagateResult::Unsat {
+                            return result;
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn vars(&self) -> Vec<usize> {
+        self.vars.clone()
+    }
+}
+
+// `value` is `array[index]`.
+#[derive(Debug, Clone)]
+pub struct ElementConstraint {
+    pub array: Vec<i32>,
+    pub index: usize,
+    pub value: usize
+}
+
+impl Constraint for ElementConstraint {
+    fn propagate(&self, domains: &mut [Domain]) -> PropagateResult {
+        let mut result = domains[self.index].update_low(0) | domains[self.index].update_high(self.array.len() as i32 - 1);
+        if result == PropagateResult::Unsat {
+            return result;
+        }
+        // Trim indices from both ends whose entries the value can't take.
+        let value = domains[self.value];
+        let supported = |i: i32| (value.low..=value.high).contains(&self.array[i as usize]);
+        let index = domains[self.index];
+        let Some(low) = (index.low..=index.high).find(|&i| supported(i)) else { return PropagateResult::Unsat; };
+        let high = (low..=index.high).rev().find(|&i| supported(i)).unwrap();
+        result = result | domains[self.index].update_low(low) | domains[self.index].update_high(high);
+        let entries = &self.array[low as usize..=high as usize];
+        let value = &mut domains[self.value];
+        result | value.update_low(*entries.iter().min().unwrap()) | value.update_high(*entries.iter().max().unwrap())
+    }
+
+    fn vars(&self) -> Vec<usize> {
+        vec![self.index, self.value]
+    }
+}
+
+// The values of `vars` are one of the rows in `tuples`.
+#[derive(Debug, Clone)]
+pub struct TableConstraint {
+    pub vars: Vec<usize>,
+    pub tuples: Vec<Vec<i32>>
+}
+
+impl Constraint for TableConstraint {
+    fn propagate(&self, domains: &mut [Domain]) -> PropagateResult {
+        let supported: Vec<&Vec<i32>> = self.tuples.iter().filter(|tuple| {
+            self.vars.iter().zip(tuple.iter()).all(|(&v, value)| (domains[v].low..=domains[v].high).contains(value))
+        }).collect();
+        if supported.is_empty() {
+            return PropagateResult::Unsat;
+        }
+        self.vars.iter().enumerate().fold(PropagateResult::NoChange, |result, (i, &v)| {
+            let low = supported.iter().map(|tuple| tuple[i]).min().unwrap();
+            let high = supported.iter().map(|tuple| tuple[i]).max().unwrap();
+            result | domains[v].update_low(low) | domains[v].update_high(high)
+        })
+    }
+
+    fn vars(&self) -> Vec<usize> {
+        self.vars.clone()
+    }
+}
+
+// `b` is 1 when the linear constraint holds and 0 when it doesn't.
+#[derive(Debug, Clone)]
+pub struct ReifiedConstraint {
+    constraint: LinearConstraint,
+    negation: LinearConstraint,
+    b: usize
+}
+
+impl ReifiedConstraint {
+    pub fn new(constraint: LinearConstraint, b: usize) -> Self {
+        ReifiedConstraint { negation: constraint.negation(), constraint, b }
+    }
+}
+
+impl Constraint for ReifiedConstraint {
+    fn propagate(&self, domains: &mut [Domain]) -> PropagateResult {
+        let result = domains[self.b].update_low(0) | domains[self.b].update_high(1);
+        if result == PropagateResult::Unsat {
+            return result;
+        }
+        if let Some(holds) = self.constraint.entailed(domains) {
+            let value = holds as i32;
+            return result | domains[self.b].update_low(value) | domains[self.b].update_high(value);
+        }
+        match (domains[self.b].low, domains[self.b].high) {
+            (1, 1) => result | self.constraint.propagate(domains),
+            (0, 0) => result | self.negation.propagate(domains),
+            _ => result
+        }
+    }
+
+    fn vars(&self) -> Vec<usize> {
+        self.constraint.terms.iter().map(|&(_, v)| v).chain([self.b]).collect()
+    }
+}
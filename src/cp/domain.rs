@@ -0,0 +1,67 @@
+// The outcome of narrowing domains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropagateResult {
+    NoChange,
+    Change,
+    // Some domain became empty, so the constraints can't all hold.
+    Unsat
+}
+
+impl std::ops::BitOr for PropagateResult {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            (PropagateResult::Unsat, _) => PropagateResult::Unsat,
+            (_, PropagateResult::Unsat) => PropagateResult::Unsat,
+            (PropagateResult::NoChange, PropagateResult::NoChange) => PropagateResult::NoChange,
+            _ => PropagateResult::Change
+        }
+    }
+}
+
+// The values a variable may still take: every integer from `low` to `high`
+// inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Domain {
+    pub low: i32,
+    pub high: i32
+}
+
+impl Domain {
+    pub fn new(low: i32, high: i32) -> Self {
+        Domain { low, high }
+    }
+
+    pub fn is_fixed(&self) -> bool {
+        self.low == self.high
+    }
+
+    pub fn size(&self) -> i64 {
+        self.high as i64 - self.low as i64 + 1
+    }
+
+    // Raises the lower bound to `value` if that narrows the domain.
+    pub fn update_low(&mut self, value: i32) -> PropagateResult {
+        if value > self.high {
+            PropagateResult::Unsat
+        } else if value > self.low {
+            self.low = value;
+            PropagateResult::Change
+        } else {
+            PropagateResult::NoChange
+        }
+    }
+
+    // Lowers the upper bound to `value` if that narrows the domain.
+    pub fn update_high(&mut self, value: i32) -> PropagateResult {
+        if value < self.low {
+            PropagateResult::Unsat
+        } else if value < self.high {
+            self.high = value;
+            PropagateResult::Change
+        } else {
+            PropagateResult::NoChange
+        }
+    }
+}
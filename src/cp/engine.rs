@@ -0,0 +1,264 @@
+use std::collections::VecDeque;
+
+use super::constraint::Constraint;
+use super::domain::{Domain, PropagateResult};
+use super::strategy::{luby, Exploration, Strategy, ValueOrder, VarSelection};
+
+// Counts of the work done by a search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    // Calls to a constraint's `propagate`.
+    pub propagations: usize,
+    // Search nodes, each a choice followed by propagation.
+    pub nodes: usize,
+    // Choices undone because they led to no (better) solution.
+    pub backtracks: usize,
+    // Times the search went back to the root to start again.
+    pub restarts: usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Goal {
+    Any,
+    Minimise(usize),
+    Maximise(usize)
+}
+
+// Depth-first search over the domains. Every change to a domain is recorded
+// on a trail, so a choice is undone by popping the trail back to where it was
+// made rather than by copying the domains. A constraint is only propagated
+// again when one of the variables it watches changes.
+pub(super) struct Engine<'a> {
+    constraints: &'a [Box<dyn Constraint>],
+    strategy: Strategy,
+    scopes: Vec<Vec<usize>>,
+    // The constraints to wake when each variable's domain changes.
+    watchers: Vec<Vec<usize>>,
+    domains: Vec<Domain>,
+    // A variable's domain before each change, most recent last.
+    trail: Vec<(usize, Domain)>,
+    queue: VecDeque<usize>,
+    queued: Vec<bool>,
+    // Reused to spot which domains a constraint changed.
+    before: Vec<Domain>,
+    // How often each constraint has failed, plus one, for dom/wdeg.
+    weights: Vec<u64>,
+    failures: usize,
+    // The failure count that ends the current restart.
+    fail_limit: Option<usize>,
+    // Whether a limit cut off part of the tree, so a search that found
+    // nothing proves nothing.
+    incomplete: bool,
+    pub stats: Stats
+}
+
+impl<'a> Engine<'a> {
+    pub fn new(domains: Vec<Domain>, constraints: &'a [Box<dyn Constraint>], strategy: Strategy) -> Self {
+        let scopes: Vec<Vec<usize>> = constraints.iter().map(|constraint| constraint.vars()).collect();
+        let mut watchers = vec![Vec::new(); domains.len()];
+        for (c, scope) in scopes.iter().enumerate() {
+            for &v in scope {
+                if watchers[v].last() != Some(&c) {
+                    watchers[v].push(c);
+                }
+            }
+        }
+        Engine {
+            constraints,
+            strategy,
+            scopes,
+            watchers,
+            domains,
+            trail: Vec::new(),
+            queue: (0..constraints.len()).collect(),
+            queued: vec![true; constraints.len()],
+            before: Vec::new(),
+            weights: vec![1; constraints.len()],
+            failures: 0,
+            fail_limit: None,
+            incomplete: false,
+            stats: Stats::default()
+        }
+    }
+
+    fn wake(&mut self, var: usize) {
+        for &c in &self.watchers[var] {
+            if !self.queued[c] {
+                self.queued[c] = true;
+                self.queue.push_back(c);
+            }
+        }
+    }
+
+    fn clear_queue(&mut self) {
+        for c in self.queue.drain(..) {
+            self.queued[c] = false;
+        }
+    }
+
+    // Narrows a domain as a search decision, returning false if it empties.
+    fn narrow(&mut self, var: usize, low: i32, high: i32) -> bool {
+        let old = self.domains[var];
+        let result = self.domains[var].update_low(low) | self.domains[var].update_high(high);
+        if self.domains[var] != old {
+            self.trail.push((var, old));
+            self.wake(var);
+        }
+        if result == PropagateResult::Unsat {
+            self.clear_queue();
+        }
+        result != PropagateResult::Unsat
+    }
+
+    // Runs queued constraints until none are left, returning false if one of
+    // them finds the domains unsatisfiable.
+    fn propagate(&mut self) -> bool {
+        while let Some(c) = self.queue.pop_front() {
+            self.queued[c] = false;
+            self.stats.propagations += 1;
+            let mut before = std::mem::take(&mut self.before);
+            before.clear();
+            before.extend(self.scopes[c].iter().map(|&v| self.domains[v]));
+            let result = self.constraints[c].propagate(&mut self.domains);
+            for (k, &old) in before.iter().enumerate() {
+                let v = self.scopes[c][k];
+                if self.domains[v] != old {
+                    self.trail.push((v, old));
+                    self.wake(v);
+                }
+            }
+            self.before = before;
+            if result == PropagateResult::Unsat {
+                self.weights[c] += 1;
+                self.clear_queue();
+                return false;
+            }
+        }
+        true
+    }
+
+    fn undo(&mut self, mark: usize) {
+        for (v, domain) in self.trail.drain(mark..).rev() {
+            self.domains[v] = domain;
+        }
+    }
+
+    // Undoes everything back to the initial domains and queues every
+    // constraint again.
+    fn restart(&mut self) {
+        self.undo(0);
+        self.clear_queue();
+        self.queue.extend(0..self.constraints.len());
+        self.queued.fill(true);
+    }
+
+    // The constraints watching `var` that still link it to another unfixed
+    // variable, counted or summed by weight.
+    fn degree(&self, var: usize, weighted: bool) -> u64 {
+        self.watchers[var].iter().filter(|&&c| {
+            self.scopes[c].iter().any(|&v| v != var && !self.domains[v].is_fixed())
+        }).map(|&c| if weighted { self.weights[c] } else { 1 }).sum()
+    }
+
+    fn choose(&self) -> Option<usize> {
+        let size = |v: usize| self.domains[v].size() as u64;
+        let unfixed = (0..self.domains.len()).filter(|&v| !self.domains[v].is_fixed());
+        match self.strategy.variable {
+            VarSelection::FirstFail => unfixed.min_by_key(|&v| size(v)),
+            VarSelection::MostConstrained => unfixed.min_by_key(|&v| (std::cmp::Reverse(self.degree(v, false)), size(v))),
+            VarSelection::DomWdeg => {
+                // Compares size / wdeg by cross multiplying, which puts a
+                // variable with no constraints left, and so no weight, last.
+                let wdeg = |v: usize| self.degree(v, true);
+                unfixed.min_by(|&a, &b| (size(a) * wdeg(b)).cmp(&(size(b) * wdeg(a))))
+            }
+        }
+    }
+
+    // The i-th choice for `var`, as the range it is narrowed to.
+    fn branch(&self, var: usize, i: usize) -> Option<(i32, i32)> {
+        let Domain { low, high } = self.domains[var];
+        let i = i as i64;
+        match self.strategy.value {
+            ValueOrder::Min => (i < self.domains[var].size()).then(|| (low + i as i32, low + i as i32)),
+            ValueOrder::Max => (i < self.domains[var].size()).then(|| (high - i as i32, high - i as i32)),
+            ValueOrder::Split => {
+                let mid = (low as i64 + high as i64).div_euclid(2) as i32;
+                [(low, mid), (mid + 1, high)].get(i as usize).copied()
+            }
+        }
+    }
+
+    // The values of a solution, or with an objective the best solution.
+    pub fn run(&mut self, goal: Goal) -> Option<Vec<i32>> {
+        let mut best = None;
+        for round in 0.. {
+            if round > 0 {
+                self.restart();
+                self.stats.restarts += 1;
+            }
+            self.incomplete = false;
+            let discrepancies = match self.strategy.exploration {
+                Exploration::DepthFirst => usize::MAX,
+                Exploration::LimitedDiscrepancy => round,
+                Exploration::LubyRestarts { scale } => {
+                    self.fail_limit = Some(self.failures + scale.max(1) * luby(round + 1));
+                    usize::MAX
+                }
+            };
+            self.search(goal, &mut best, discrepancies);
+            if !self.incomplete || (goal == Goal::Any && best.is_some()) {
+                break;
+            }
+        }
+        best
+    }
+
+    // Counts a failed node, returning true if that uses up the current
+    // restart's failures.
+    fn fail(&mut self) -> bool {
+        self.failures += 1;
+        let exhausted = self.fail_limit.is_some_and(|limit| self.failures >= limit);
+        self.incomplete |= exhausted;
+        exhausted
+    }
+
+    // Searches below the current domains, taking choices worth at most
+    // `discrepancies`, and returns true once the search should stop. Each
+    // solution replaces `best`, and with an objective every later node must
+    // beat it.
+    fn search(&mut self, goal: Goal, best: &mut Option<Vec<i32>>, discrepancies: usize) -> bool {
+        self.stats.nodes += 1;
+        if let Some(best) = best {
+            let feasible = match goal {
+                Goal::Any => true,
+                Goal::Minimise(v) => best[v].checked_sub(1).is_some_and(|high| self.narrow(v, i32::MIN, high)),
+                Goal::Maximise(v) => best[v].checked_add(1).is_some_and(|low| self.narrow(v, low, i32::MAX))
+            };
+            if !feasible {
+                return self.fail();
+            }
+        }
+        if !self.propagate() {
+            return self.fail();
+        }
+        let Some(var) = self.choose() else {
+            *best = Some(self.domains.iter().map(|domain| domain.low).collect());
+            return goal == Goal::Any;
+        };
+        for i in 0.. {
+            let Some((low, high)) = self.branch(var, i) else { break; };
+            if i > discrepancies {
+                self.incomplete = true;
+                break;
+            }
+            let mark = self.trail.len();
+            if self.narrow(var, low, high) && self.search(goal, best, discrepancies - i) {
+                return true;
+            }
+            self.undo(mark);
+            self.stats.backtracks += 1;
+        }
+        false
+    }
+}
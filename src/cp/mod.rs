@@ -0,0 +1,525 @@
+// A small constraint programming solver over integer variables.
+//
+// Each variable has a Domain of values it may take. Constraints narrow the
+// domains by propagation until nothing changes, with a constraint only run
+// again once a variable it watches has changed. The search then picks a
+// variable and narrows it to each of its choices in turn, propagating again
+// after each choice and undoing the choice's changes from a trail when it
+// leads nowhere. By default it fixes the variable with the smallest domain to
+// each of its values from the largest down; a Strategy picks other
+// heuristics, limited discrepancy search or restarts. Solver::stats reports
+// the work done.
+//
+//     let mut solver = Solver::new();
+//     let x = solver.add_var(0, 10);
+//     let y = solver.add_var(3, 10);
+//     let total = solver.constant(12);
+//     solver.sum_eq(&[x, y], total);
+//     let best = solver.minimise(y).unwrap();
+//     assert_eq!((best[x], best[y]), (9, 3));
+
+mod constraint;
+mod domain;
+mod engine;
+mod strategy;
+
+pub use constraint::{
+    AllDifferentConstraint, Constraint, ElementConstraint, LinearConstraint, ReifiedConstraint, Relation, SumConstraint,
+    TableConstraint
+};
+pub use domain::{Domain, PropagateResult};
+pub use engine::Stats;
+pub use strategy::{Exploration, Strategy, ValueOrder, VarSelection};
+
+use engine::{Engine, Goal};
+use std::cell::Cell;
+use std::ops::Index;
+
+// A handle to one of a Solver's variables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Var {
+    index: usize
+}
+
+impl Var {
+    // The variable's position in the domains passed to constraints.
+    pub fn index(self) -> usize {
+        self.index
+    }
+}
+
+// A value for every variable that satisfies all the constraints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment {
+    values: Vec<i32>
+}
+
+impl Assignment {
+    pub fn values(&self) -> &[i32] {
+        &self.values
+    }
+}
+
+impl Index<Var> for Assignment {
+    type Output = i32;
+
+    fn index(&self, var: Var) -> &i32 {
+        &self.values[var.index]
+    }
+}
+
+// Variables and the constraints between them.
+#[derive(Default)]
+pub struct Solver {
+    domains: Vec<Domain>,
+    constraints: Vec<Box<dyn Constraint>>,
+    strategy: Strategy,
+    stats: Cell<Stats>
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // A new variable taking a value from `low` to `high` inclusive.
+    pub fn add_var(&mut self, low: i32, high: i32) -> Var {
+        let index = self.domains.len();
+        self.domains.push(Domain::new(low, high));
+        Var { index }
+    }
+
+    // A new variable fixed to `value`.
+    pub fn constant(&mut self, value: i32) -> Var {
+        self.add_var(value, value)
+    }
+
+    pub fn domain(&self, var: Var) -> Domain {
+        self.domains[var.index]
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.domains.len()
+    }
+
+    // Adds a constraint of any kind. It sees the domains indexed by
+    // Var::index.
+    pub fn add_constraint(&mut self, constraint: impl Constraint + 'static) {
+        self.constraints.push(Box::new(constraint));
+    }
+
+    // Requires the variables in `vars` to add up to `dst`.
+    pub fn sum_eq(&mut self, vars: &[Var], dst: Var) {
+        let vars = vars.iter().map(|var| var.index).collect();
+        self.add_constraint(SumConstraint { vars, dst: dst.index });
+    }
+
+    // Requires `sum(coefficient * var) relation rhs` over the `terms`.
+    pub fn linear(&mut self, terms: &[(i32, Var)], relation: Relation, rhs: i32) {
+        self.add_constraint(linear(terms, relation, rhs));
+    }
+
+    // Requires `a` and `b` to take different values.
+    pub fn not_equal(&mut self, a: Var, b: Var) {
+        self.linear(&[(1, a), (-1, b)], Relation::Ne, 0);
+    }
+
+    // Requires the variables in `vars` to take pairwise different values.
+    pub fn all_different(&mut self, vars: &[Var]) {
+        let vars = vars.iter().map(|var| var.index).collect();
+        self.add_constraint(AllDifferentConstraint { vars });
+    }
+
+    // Requires `value` to equal `array[index]`, restricting `index` to the
+    // array's positions.
+    pub fn element(&mut self, array: &[i32], index: Var, value: Var) {
+        self.add_constraint(ElementConstraint { array: array.to_vec(), index: index.index, value: value.index });
+    }
+
+    // Requires the values of `vars` to match one of the `tuples`.
+    pub fn table(&mut self, vars: &[Var], tuples: &[Vec<i32>]) {
+        assert!(tuples.iter().all(|tuple| tuple.len() == vars.len()), "table rows must have a value for every variable");
+        let vars = vars.iter().map(|var| var.index).collect();
+        self.add_constraint(TableConstraint { vars, tuples: tuples.to_vec() });
+    }
+
+    // A new 0/1 variable that is 1 exactly when `sum(coefficient * var)
+    // relation rhs` holds.
+    pub fn reify(&mut self, terms: &[(i32, Var)], relation: Relation, rhs: i32) -> Var {
+        let b = self.add_var(0, 1);
+        self.add_constraint(ReifiedConstraint::new(linear(terms, relation, rhs), b.index));
+        b
+    }
+
+    // Any assignment satisfying every constraint, or None if there isn't one.
+    pub fn solve(&self) -> Option<Assignment> {
+        self.run(Goal::Any)
+    }
+
+    // An assignment satisfying every constraint with `var` as small as
+    // possible, or None if the constraints can't be satisfied.
+    pub fn minimise(&self, var: Var) -> Option<Assignment> {
+        self.run(Goal::Minimise(var.index))
+    }
+
+    // An assignment satisfying every constraint with `var` as large as
+    // possible, or None if the constraints can't be satisfied.
+    pub fn maximise(&self, var: Var) -> Option<Assignment> {
+        self.run(Goal::Maximise(var.index))
+    }
+
+    // Sets how the search branches and explores the tree.
+    pub fn set_strategy(&mut self, strategy: Strategy) {
+        self.strategy = strategy;
+    }
+
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    // The work done by the most recent `solve`, `minimise` or `maximise`.
+    pub fn stats(&self) -> Stats {
+        self.stats.get()
+    }
+
+    fn run(&self, goal: Goal) -> Option<Assignment> {
+        let mut engine = Engine::new(self.domains.clone(), &self.constraints, self.strategy);
+        let values = engine.run(goal);
+        self.stats.set(engine.stats);
+        values.map(|values| Assignment { values })
+    }
+}
+
+fn linear(terms: &[(i32, Var)], relation: Relation, rhs: i32) -> LinearConstraint {
+    LinearConstraint { terms: terms.iter().map(|&(a, var)| (a, var.index)).collect(), relation, rhs: rhs as i64 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    // Sums a solution's values by brute force to check the solver's answer.
+    fn check_sums(solver: &Solver, sums: &[(Vec<Var>, Var)], assignment: &Assignment) {
+        for (vars, dst) in sums {
+            assert_eq!(vars.iter().map(|&v| assignment[v]).sum::<i32>(), assignment[*dst]);
+        }
+        for i in 0..solver.num_vars() {
+            let domain = solver.domains[i];
+            assert!(domain.low <= assignment.values()[i] && assignment.values()[i] <= domain.high);
+        }
+    }
+
+    #[test]
+    fn propagation_narrows_sums() {
+        let mut domains = vec![Domain::new(0, 10), Domain::new(0, 10), Domain::new(15, 15)];
+        let sum = SumConstraint { vars: vec![0, 1], dst: 2 };
+        assert_eq!(sum.propagate(&mut domains), PropagateResult::Change);
+        assert_eq!(domains[..2], [Domain::new(5, 10), Domain::new(5, 10)]);
+        assert_eq!(sum.propagate(&mut domains), PropagateResult::NoChange);
+        domains[2] = Domain::new(25, 30);
+        assert_eq!(sum.propagate(&mut domains), PropagateResult::Unsat);
+    }
+
+    #[test]
+    fn extreme_bounds_dont_overflow() {
+        let mut domains = vec![Domain::new(0, i32::MAX), Domain::new(0, i32::MAX), Domain::new(i32::MAX, i32::MAX)];
+        let sum = SumConstraint { vars: vec![0, 1], dst: 2 };
+        assert_eq!(sum.propagate(&mut domains), PropagateResult::NoChange);
+        let le = LinearConstraint { terms: vec![(1, 0)], relation: Relation::Le, rhs: i32::MAX as i64 };
+        assert_eq!(le.entailed(&domains), Some(true));
+        assert_eq!(le.negation().entailed(&domains), Some(false));
+        let le = LinearConstraint { terms: vec![(1, 0)], relation: Relation::Le, rhs: i64::MAX };
+        assert_eq!(le.negation().entailed(&domains), Some(false));
+    }
+
+    #[test]
+    fn solve_finds_a_satisfying_assignment() {
+        let mut solver = Solver::new();
+        let vars: Vec<Var> = (0..4).map(|_| solver.add_var(0, 9)).collect();
+        let (a, b) = (solver.constant(13), solver.constant(7));
+        let sums = [(vars[..3].to_vec(), a), (vars[2..].to_vec(), b)];
+        for (vars, dst) in &sums {
+            solver.sum_eq(vars, *dst);
+        }
+        check_sums(&solver, &sums, &solver.solve().unwrap());
+    }
+
+    #[test]
+    fn unsatisfiable() {
+        let mut solver = Solver::new();
+        let (x, y) = (solver.add_var(0, 3), solver.add_var(0, 3));
+        let total = solver.constant(7);
+        solver.sum_eq(&[x, y], total);
+        assert_eq!(solver.solve(), None);
+        assert_eq!(solver.minimise(x), None);
+    }
+
+    #[test]
+    fn optimisation() {
+        // x + y = 10 and y + z = x with all three in 0..=8.
+        let mut solver = Solver::new();
+        let (x, y, z) = (solver.add_var(0, 8), solver.add_var(0, 8), solver.add_var(0, 8));
+        let ten = solver.constant(10);
+        solver.sum_eq(&[x, y], ten);
+        solver.sum_eq(&[y, z], x);
+        let lowest = solver.minimise(x).unwrap();
+        assert_eq!((lowest[x], lowest[y], lowest[z]), (5, 5, 0));
+        let highest = solver.maximise(x).unwrap();
+        assert_eq!((highest[x], highest[y], highest[z]), (8, 2, 6));
+        assert_eq!(solver.domain(x), Domain::new(0, 8));
+    }
+
+    #[test]
+    fn weighted_linear_bounds() {
+        let mut domains = vec![Domain::new(0, 10), Domain::new(0, 10)];
+        let le = LinearConstraint { terms: vec![(2, 0), (3, 1)], relation: Relation::Le, rhs: 12 };
+        assert_eq!(le.propagate(&mut domains), PropagateResult::Change);
+        assert_eq!(domains, [Domain::new(0, 6), Domain::new(0, 4)]);
+        let ge = LinearConstraint { terms: vec![(1, 0), (1, 1)], relation: Relation::Ge, rhs: 9 };
+        assert_eq!(ge.propagate(&mut domains), PropagateResult::Change);
+        assert_eq!(domains, [Domain::new(5, 6), Domain::new(3, 4)]);
+        let mut domains = vec![Domain::new(0, 5), Domain::new(0, 5)];
+        let eq = LinearConstraint { terms: vec![(1, 0), (-2, 1)], relation: Relation::Eq, rhs: 3 };
+        assert_eq!(eq.propagate(&mut domains), PropagateResult::Change);
+        assert_eq!(domains, [Domain::new(3, 5), Domain::new(0, 1)]);
+        assert_eq!(eq.negation().relation, Relation::Ne);
+        let mut domains = vec![Domain::new(4, 4), Domain::new(4, 9)];
+        let ne = LinearConstraint { terms: vec![(1, 0), (-1, 1)], relation: Relation::Ne, rhs: 0 };
+        assert_eq!(ne.propagate(&mut domains), PropagateResult::Change);
+        assert_eq!(domains[1], Domain::new(5, 9));
+    }
+
+    #[test]
+    fn send_more_money() {
+        let mut solver = Solver::new();
+        let [s, e, n, d, m, o, r, y] = std::array::from_fn(|_| solver.add_var(0, 9));
+        solver.all_different(&[s, e, n, d, m, o, r, y]);
+        solver.linear(&[(1, s), (-1, m)], Relation::Ge, 1);
+        solver.linear(&[(1, m)], Relation::Ge, 1);
+        solver.linear(&[
+            (1000, s), (100, e), (10, n), (1, d),
+            (1000, m), (100, o), (10, r), (1, e),
+            (-10000, m), (-1000, o), (-100, n), (-10, e), (-1, y)
+        ], Relation::Eq, 0);
+        let solution = solver.solve().unwrap();
+        assert_eq!([s, e, n, d, m, o, r, y].map(|v| solution[v]), [9, 5, 6, 7, 1, 0, 8, 2]);
+    }
+
+    #[test]
+    fn hall_intervals() {
+        // x and y share {1, 2}, so z can't use either.
+        let mut domains = vec![Domain::new(1, 2), Domain::new(1, 2), Domain::new(1, 4)];
+        let different = AllDifferentConstraint { vars: vec![0, 1, 2] };
+        assert_eq!(different.propagate(&mut domains), PropagateResult::Change);
+        assert_eq!(domains[2], Domain::new(3, 4));
+        domains[2] = Domain::new(2, 2);
+        assert_eq!(different.propagate(&mut domains), PropagateResult::Unsat);
+    }
+
+    #[test]
+    fn element_and_table() {
+        let mut solver = Solver::new();
+        let (i, price) = (solver.add_var(-5, 20), solver.add_var(0, 100));
+        solver.element(&[40, 10, 30, 20, 50], i, price);
+        solver.linear(&[(1, price)], Relation::Le, 25);
+        assert_eq!(solver.maximise(i).map(|best| (best[i], best[price])), Some((3, 20)));
+        assert_eq!(solver.minimise(price).map(|best| best[i]), Some(1));
+        let mut solver = Solver::new();
+        let (x, y) = (solver.add_var(0, 9), solver.add_var(0, 9));
+        solver.table(&[x, y], &[vec![1, 5], vec![2, 7], vec![3, 2]]);
+        solver.linear(&[(1, y)], Relation::Ge, 4);
+        assert_eq!(solver.maximise(x).map(|best| (best[x], best[y])), Some((2, 7)));
+    }
+
+    #[test]
+    fn reification_counts_satisfied_constraints() {
+        // The largest number of x, y and z that can be at least 3 when they
+        // add up to 7 and x and y differ.
+        let mut solver = Solver::new();
+        let vars = [solver.add_var(0, 5), solver.add_var(0, 5), solver.add_var(0, 5)];
+        let total = solver.constant(7);
+        solver.sum_eq(&vars, total);
+        solver.not_equal(vars[0], vars[1]);
+        let flags: Vec<Var> = vars.iter().map(|&v| solver.reify(&[(1, v)], Relation::Ge, 3)).collect();
+        let count = solver.add_var(0, 3);
+        solver.sum_eq(&flags, count);
+        let best = solver.maximise(count).unwrap();
+        assert_eq!(best[count], 2);
+        for (&v, &flag) in vars.iter().zip(&flags) {
+            assert_eq!(best[flag], (best[v] >= 3) as i32);
+        }
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = crate::generator::Rng::new(23);
+        let relations = [Relation::Eq, Relation::Ne, Relation::Le, Relation::Ge];
+        for _ in 0..300 {
+            let mut solver = Solver::new();
+            let vars: Vec<Var> = (0..3).map(|_| solver.add_var(0, 4)).collect();
+            let mut constraints = Vec::new();
+            for _ in 0..rng.range(1, 3) {
+                let terms: Vec<(i32, Var)> = vars.iter().map(|&v| (rng.range(0, 7) as i32 - 3, v)).collect();
+                let relation = relations[rng.range(0, 3) as usize];
+                let rhs = rng.range(0, 12) as i32 - 4;
+                if rng.range(0, 1) == 0 {
+                    solver.linear(&terms, relation, rhs);
+                    constraints.push((terms, relation, rhs, None));
+                } else {
+                    let b = solver.reify(&terms, relation, rhs);
+                    constraints.push((terms, relation, rhs, Some(b)));
+                }
+            }
+            let holds = |values: &[i32], terms: &[(i32, Var)], relation: Relation, rhs: i32| {
+                let lhs: i32 = terms.iter().map(|&(a, v)| a * values[v.index()]).sum();
+                relation.holds(lhs as i64, rhs as i64)
+            };
+            let brute_force = (0..125).any(|n| {
+                let values = [n % 5, n / 5 % 5, n / 25];
+                constraints.iter().all(|(terms, relation, rhs, b)| b.is_some() || holds(&values, terms, *relation, *rhs))
+            });
+            let solution = solver.solve();
+            assert_eq!(solution.is_some(), brute_force);
+            if let Some(solution) = solution {
+                for (terms, relation, rhs, b) in &constraints {
+                    let satisfied = holds(solution.values(), terms, *relation, *rhs);
+                    assert_eq!(satisfied, b.is_none_or(|b| solution[b] == 1));
+                }
+            }
+        }
+    }
+
+    // Counts its propagations into a shared cell.
+    struct Counting {
+        var: usize,
+        count: Rc<Cell<usize>>
+    }
+
+    impl Constraint for Counting {
+        fn propagate(&self, domains: &mut [Domain]) -> PropagateResult {
+            self.count.set(self.count.get() + 1);
+            domains[self.var].update_low(3)
+        }
+
+        fn vars(&self) -> Vec<usize> {
+            vec![self.var]
+        }
+    }
+
+    #[test]
+    fn only_watching_constraints_wake() {
+        let count = Rc::new(Cell::new(0));
+        let mut solver = Solver::new();
+        let (x, y, z) = (solver.add_var(0, 3), solver.add_var(0, 3), solver.add_var(0, 3));
+        solver.add_constraint(Counting { var: z.index(), count: count.clone() });
+        solver.linear(&[(1, x), (1, y)], Relation::Le, 4);
+        let best = solver.maximise(x).unwrap();
+        assert_eq!((best[x], best[z]), (3, 3));
+        // The counter fixes z at the root, runs once more to see nothing left
+        // to do, and then never sees z change again.
+        assert_eq!(count.get(), 2);
+        let stats = solver.stats();
+        assert!(stats.nodes > 1 && stats.propagations > 1);
+        solver.solve();
+        assert_eq!(count.get(), 4);
+        assert_eq!(solver.stats().backtracks, 0);
+    }
+
+    #[test]
+    fn stats_count_backtracks() {
+        // Three pigeons in two holes fails only after trying every hole for
+        // the first two.
+        let mut solver = Solver::new();
+        let pigeons = [solver.add_var(0, 1), solver.add_var(0, 1), solver.add_var(0, 1)];
+        for (i, &a) in pigeons.iter().enumerate() {
+            for &b in &pigeons[i + 1..] {
+                solver.not_equal(a, b);
+            }
+        }
+        assert_eq!(solver.solve(), None);
+        let stats = solver.stats();
+        assert_eq!((stats.nodes, stats.backtracks), (3, 2));
+        assert_eq!(solver.domain(pigeons[0]), Domain::new(0, 1));
+    }
+
+    fn strategies() -> Vec<Strategy> {
+        let variables = [VarSelection::FirstFail, VarSelection::MostConstrained, VarSelection::DomWdeg];
+        let values = [ValueOrder::Min, ValueOrder::Max, ValueOrder::Split];
+        let explorations = [
+            Exploration::DepthFirst,
+            Exploration::LimitedDiscrepancy,
+            Exploration::LubyRestarts { scale: 1 },
+            Exploration::LubyRestarts { scale: 5 }
+        ];
+        let mut strategies = Vec::new();
+        for variable in variables {
+            for value in values {
+                strategies.extend(explorations.map(|exploration| Strategy { variable, value, exploration }));
+            }
+        }
+        strategies
+    }
+
+    #[test]
+    fn every_strategy_finds_the_optimum() {
+        let mut rng = crate::generator::Rng::new(25);
+        for _ in 0..40 {
+            let mut solver = Solver::new();
+            let vars: Vec<Var> = (0..3).map(|_| solver.add_var(0, 4)).collect();
+            let mut rows = Vec::new();
+            for _ in 0..2 {
+                let terms: Vec<(i32, Var)> = vars.iter().map(|&v| (rng.range(0, 5) as i32 - 2, v)).collect();
+                let rhs = rng.range(0, 8) as i32 - 2;
+                solver.linear(&terms, Relation::Le, rhs);
+                rows.push((terms, rhs));
+            }
+            solver.not_equal(vars[1], vars[2]);
+            let objective = solver.add_var(-40, 40);
+            solver.linear(&[(2, vars[0]), (-3, vars[1]), (1, vars[2]), (-1, objective)], Relation::Eq, 0);
+            let brute_force = (0..125).filter_map(|n| {
+                let values = [n % 5, n / 5 % 5, n / 25];
+                let feasible = values[1] != values[2] && rows.iter().all(|(terms, rhs)| {
+                    terms.iter().map(|&(a, v)| a * values[v.index()]).sum::<i32>() <= *rhs
+                });
+                feasible.then_some(2 * values[0] - 3 * values[1] + values[2])
+            }).min();
+            for strategy in strategies() {
+                solver.set_strategy(strategy);
+                assert_eq!(solver.minimise(objective).map(|best| best[objective]), brute_force, "{:?}", strategy);
+            }
+        }
+    }
+
+    #[test]
+    fn discrepancies_and_restarts() {
+        // Five pigeons in four holes takes many failures to refute.
+        let mut solver = Solver::new();
+        let pigeons: Vec<Var> = (0..5).map(|_| solver.add_var(0, 3)).collect();
+        for (i, &a) in pigeons.iter().enumerate() {
+            for &b in &pigeons[i + 1..] {
+                solver.not_equal(a, b);
+            }
+        }
+        let depth_first = solver.solve();
+        let nodes = solver.stats().nodes;
+        assert_eq!((depth_first, solver.stats().restarts), (None, 0));
+        solver.set_strategy(Strategy { exploration: Exploration::LubyRestarts { scale: 1 }, ..Strategy::default() });
+        assert_eq!(solver.solve(), None);
+        assert!(solver.stats().restarts > 0 && solver.stats().nodes > nodes);
+        // Proving there's no solution takes every discrepancy count up to
+        // the one where nothing is cut off.
+        solver.set_strategy(Strategy { exploration: Exploration::LimitedDiscrepancy, ..Strategy::default() });
+        assert_eq!(solver.solve(), None);
+        assert!(solver.stats().restarts >= 3);
+        // A solution on the heuristic's first path needs no more than the
+        // first round of discrepancy search.
+        let mut solver = Solver::new();
+        let (x, y) = (solver.add_var(0, 9), solver.add_var(0, 9));
+        solver.linear(&[(1, x), (1, y)], Relation::Le, 12);
+        solver.set_strategy(Strategy { exploration: Exploration::LimitedDiscrepancy, ..Strategy::default() });
+        let solution = solver.solve().unwrap();
+        assert_eq!((solution[x], solution[y], solver.stats().restarts), (9, 3, 0));
+        assert_eq!(solver.strategy().exploration, Exploration::LimitedDiscrepancy);
+    }
+}
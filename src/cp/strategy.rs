@@ -0,0 +1,71 @@
+// Which unfixed variable the search branches on next.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VarSelection {
+    // The smallest domain, so failures show up early.
+    #[default]
+    FirstFail,
+    // The most constraints still linking it to other unfixed variables,
+    // breaking ties by the smallest domain.
+    MostConstrained,
+    // The smallest domain relative to the weighted degree, where each
+    // constraint's weight counts the times it failed. The search learns
+    // which constraints are hard, which pays off most with restarts.
+    DomWdeg
+}
+
+// The order the search tries a variable's values in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValueOrder {
+    // Smallest value first.
+    Min,
+    // Largest value first.
+    #[default]
+    Max,
+    // Halves the domain, trying the lower half first.
+    Split
+}
+
+// How the search tree is explored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Exploration {
+    // Plain depth-first search.
+    #[default]
+    DepthFirst,
+    // Limited discrepancy search: first only the paths that always take the
+    // heuristic's first choice, then those straying from it once, twice and
+    // so on. Taking a branch's i-th choice costs i discrepancies.
+    LimitedDiscrepancy,
+    // Restarts from the root after `scale` times the next term of the Luby
+    // sequence (1, 1, 2, 1, 1, 2, 4, ...) failures. The best solution so far
+    // and the VarSelection::DomWdeg weights carry over between runs.
+    LubyRestarts { scale: usize }
+}
+
+// The search settings of a Solver. The default is
+// first-fail with the largest value first, depth first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Strategy {
+    pub variable: VarSelection,
+    pub value: ValueOrder,
+    pub exploration: Exploration
+}
+
+// The i-th term of the Luby sequence, counting from 1.
+pub(super) fn luby(i: usize) -> usize {
+    let mut k = 1;
+    while (1 << k) - 1 < i {
+        k += 1;
+    }
+    if (1 << k) - 1 == i { 1 << (k - 1) } else { luby(i - (1 << (k - 1)) + 1) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn luby_sequence() {
+        let terms: Vec<usize> = (1..=15).map(luby).collect();
+        assert_eq!(terms, [1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+    }
+}
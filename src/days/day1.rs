@@ -0,0 +1,139 @@
+use crate::parse::{self, Line, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::util::number::modulo;
+
+pub struct Day1;
+
+enum Direction {
+    Left,
+    Right
+}
+
+pub struct Rotation {
+    direction: Direction,
+    steps: u32
+}
+
+impl Rotation {
+    fn parse(line: Line) -> Result<Self, ParseError> {
+        let Some(dir) = line.text.chars().next() else { return Err(line.error_at_end("'L' or 'R'")); };
+        let (dir_str, steps_str) = line.text.split_at(dir.len_utf8());
+        let direction = match dir {
+            'L' => Direction::Left,
+            'R' => Direction::Right,
+            _ => return Err(line.error(dir_str, "'L' or 'R'"))
+        };
+        let steps = line.parse::<u32>(steps_str, "a number of steps")?;
+        Ok(Rotation { direction, steps })
+    }
+
+    fn steps_right(&self) -> i32 {
+        match self.direction {
+            Direction::Left => -(self.steps as i32),
+            Direction::Right => self.steps as i32
+        }
+    }
+}
+
+pub fn count_zeros(rotations: &[Rotation]) -> usize {
+    rotations.iter().scan(50, |acc, rotation| {
+        *acc += rotation.steps_right();
+        *acc = modulo(*acc, 100);
+        Some(*acc)
+    }).filter(|&x| x == 0).count()
+}
+
+pub fn count_tmp_zeros(rotations: &[Rotation]) -> usize {
+    rotations.iter().scan(50, |acc, rotation| {
+        if rotation.steps == 0 {
+            return Some(0);
+        }
+        let steps_right = rotation.steps_right();
+        let initial_position = *acc;
+        *acc += steps_right;
+        let tmp_zeros =
+            if *acc == 0 {
+                1
+            } else if *acc < 0 {
+                -(*acc / 100) + (initial_position != 0) as i32
+            } else {
+                *acc / 100
+            };
+        *acc = modulo(*acc, 100);
+        Some(tmp_zeros)
+    }).sum::<i32>() as usize
+}
+
+// Slow reference for count_tmp_zeros which turns the dial one click at a time.
+pub fn count_tmp_zeros_by_clicking(rotations: &[Rotation]) -> usize {
+    let mut position = 50;
+    let mut zeros = 0;
+    for rotation in rotations {
+        let click = rotation.steps_right().signum();
+        for _ in 0..rotation.steps {
+            position = modulo(position + click, 100);
+            if position == 0 {
+                zeros += 1;
+            }
+        }
+    }
+    zeros
+}
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    type Input = Vec<Rotation>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(input).map(Rotation::parse).collect()
+    }
+    fn part1(&self, input: &Self::Input) -> Answer {
+        count_zeros(input).into()
+    }
+    fn part2(&self, input: &Self::Input) -> Answer {
+        count_tmp_zeros(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rotations(lines: &[&str]) -> Vec<Rotation> {
+        lines.iter().enumerate().map(|(i, text)| Rotation::parse(Line { number: i + 1, text }).unwrap()).collect()
+    }
+
+    #[test]
+    fn count_tmp_zeros_landing_on_zero() {
+        assert_eq!(count_tmp_zeros(&rotations(&["L50"])), 1);
+        assert_eq!(count_tmp_zeros(&rotations(&["R50"])), 1);
+        assert_eq!(count_tmp_zeros(&rotations(&["L49"])), 0);
+    }
+
+    #[test]
+    fn count_tmp_zeros_leaving_zero() {
+        assert_eq!(count_tmp_zeros(&rotations(&["L50", "R5"])), 1);
+        assert_eq!(count_tmp_zeros(&rotations(&["L50", "L5"])), 1);
+        assert_eq!(count_tmp_zeros(&rotations(&["L50", "L100"])), 2);
+    }
+
+    #[test]
+    fn count_tmp_zeros_full_turns() {
+        assert_eq!(count_tmp_zeros(&rotations(&["R1000"])), 10);
+        assert_eq!(count_tmp_zeros(&rotations(&["L150"])), 2);
+        assert_eq!(count_tmp_zeros(&rotations(&["L250"])), 3);
+    }
+
+    #[test]
+    fn count_tmp_zeros_no_steps() {
+        assert_eq!(count_tmp_zeros(&rotations(&["L50", "R0"])), 1);
+        assert_eq!(count_tmp_zeros(&rotations(&["L0"])), 0);
+    }
+
+    #[test]
+    fn count_zeros_example() {
+        let input = rotations(&["L68", "L30", "R48", "L5", "R60", "L55", "L1", "L99", "R14", "L82"]);
+        assert_eq!(count_zeros(&input), 3);
+        assert_eq!(count_tmp_zeros(&input), 6);
+    }
+}
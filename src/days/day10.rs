@@ -0,0 +1,200 @@
+use crate::cp::{Solver, Var};
+use crate::gf2::{BitVec, LinearSystem};
+use crate::ilp::{Ilp, Outcome};
+use crate::parse::{self, first_char, Line, ParseError};
+use crate::search;
+use crate::solution::{Answer, Solution};
+
+pub struct Day10;
+
+#[derive(Debug)]
+pub struct Problem {
+    light_goal: Vec::<bool>,
+    joltage_goal: Vec::<u32>,
+    buttons: Vec::<Vec::<usize>>
+}
+
+fn parse_light_index(line: Line, index: &str, num_lights: usize) -> Result<usize, ParseError> {
+    let i = line.parse::<usize>(index, "a light index")?;
+    if i < num_lights { Ok(i) } else { Err(line.error(index, format!("a light index below {}", num_lights))) }
+}
+
+fn parse_problem(line: Line) -> Result<Problem, ParseError> {
+    let (light_text, mut text) = line.bracketed(line.text, '[', ']')?;
+    let light_goal = light_text.char_indices().map(|(i, c)| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(line.error(first_char(&light_text[i..]), "'#' or '.'"))
+    }).collect::<Result<Vec::<bool>, ParseError>>()?;
+    let mut buttons = Vec::<Vec::<usize>>::new();
+    while text.trim_start().starts_with('(') {
+        let (button, rest) = line.bracketed(text, '(', ')')?;
+        buttons.push(line.list(button, ",", |i| parse_light_index(line, i, light_goal.len()))?);
+        text = rest;
+    }
+    let (joltage_goal, rest) = line.bracketed(text, '{', '}')?;
+    if !rest.trim().is_empty() {
+        return Err(line.error(rest.trim(), "end of line"));
+    }
+    let joltages = line.list(joltage_goal, ",", |n| line.parse::<u32>(n, "a joltage"))?;
+    if joltages.len() != light_goal.len() {
+        return Err(line.error(joltage_goal, format!("{} joltages", light_goal.len())));
+    }
+    Ok(Problem{light_goal, joltage_goal: joltages, buttons})
+}
+
+fn toggle(state: &[bool], indices: &[usize]) -> Vec::<bool> {
+    let mut state = state.to_vec();
+    for &i in indices {
+        state[i] = !state[i];
+    }
+    state
+}
+
+pub struct LightPresses {
+    pub count: usize,
+    pub buttons: Vec<usize>
+}
+
+// Pressing a button twice undoes it, so each button is pressed at most once
+// and light i ends up lit when the presses of the buttons wired to it add up
+// to its goal modulo 2. That gives one equation over GF(2) per light.
+fn lights_system(problem: &Problem) -> LinearSystem {
+    let num_buttons = problem.buttons.len();
+    let mut system = LinearSystem::new(num_buttons);
+    for (light, &lit) in problem.light_goal.iter().enumerate() {
+        let wired = (0..num_buttons).filter(|&i| problem.buttons[i].contains(&light));
+        system.add_equation(&BitVec::from_indices(num_buttons, wired), lit);
+    }
+    system
+}
+
+// The fewest buttons to press to light the goal pattern, or None if no
+// combination does.
+pub fn solve_lights(problem: &Problem) -> Option<LightPresses> {
+    let presses = lights_system(problem).solve()?.min_weight();
+    Some(LightPresses { count: presses.count_ones(), buttons: presses.iter_ones().collect() })
+}
+
+// Slow reference for solve_lights which searches breadth first through the
+// patterns reachable by pressing buttons.
+pub fn solve_lights_by_search(problem: &Problem) -> Option<usize> {
+    let initial = vec![false; problem.light_goal.len()];
+    let result = search::bfs(initial, |state| {
+        problem.buttons.iter().enumerate().map(|(i, button)| (i, toggle(state, button))).collect::<Vec<_>>()
+    }, |state| *state == problem.light_goal);
+    result.path.map(|path| path.cost)
+}
+
+// The fewest presses to bring every counter to its goal, or why there is no
+// fewest. Each counter's goal is the sum of the presses of the buttons wired
+// to it, so this is an integer linear program minimising the total.
+pub fn solve_joltage(problem: &Problem) -> Result<usize, String> {
+    let num_buttons = problem.buttons.len();
+    let mut ilp = Ilp::new(vec![1; num_buttons]);
+    for (counter, &goal) in problem.joltage_goal.iter().enumerate() {
+        let wired = (0..num_buttons).map(|i| problem.buttons[i].contains(&counter) as i64).collect();
+        ilp.add_equation(wired, goal as i64);
+    }
+    // No button can be pressed more often than the lowest goal it feeds.
+    for (i, button) in problem.buttons.iter().enumerate() {
+        let most = button.iter().map(|&counter| problem.joltage_goal[counter] as i64).min().unwrap_or(0);
+        ilp.set_bounds(i, 0, Some(most));
+    }
+    match ilp.minimise().outcome {
+        Outcome::Optimal(solution) => Ok(solution.value as usize),
+        Outcome::Infeasible => Err("can't reach its joltage goal".to_string()),
+        Outcome::Unbounded => Err("has no fewest joltage presses".to_string()),
+        Outcome::Overflow => Err("needs more joltage presses than fit in 64 bits".to_string())
+    }
+}
+
+// Slow reference for solve_joltage using the constraint solver. Only
+// practical for small goals.
+pub fn solve_joltage_by_search(problem: &Problem) -> Option<usize> {
+    let mut solver = Solver::new();
+    let num_buttons = problem.buttons.len();
+    let num_counters = problem.joltage_goal.len();
+    let mut counters_to_buttons = vec!(Vec::<usize>::default();num_counters);
+    for (i, counters) in problem.buttons.iter().enumerate() {
+        for &counter in counters {
+            counters_to_buttons[counter].push(i)
+        }
+    }
+    let vars: Vec<Var> = (0..num_buttons).map(|_| {
+        solver.add_var(0, 100000)
+    }).collect();
+    for (&goal, buttons) in problem.joltage_goal.iter().zip(counters_to_buttons) {
+        let sum = solver.constant(goal as i32);
+        let vars: Vec<Var> = buttons.iter().map(|&i| vars[i]).collect();
+        solver.sum_eq(&vars, sum);
+    }
+    let sum = solver.add_var(0, 100000);
+    solver.sum_eq(&vars, sum);
+    solver.minimise(sum).map(|best| best[sum] as usize)
+}
+
+// The total presses over all machines, or why the first machine without an
+// answer has none.
+fn total_presses(input: &[Problem], presses: impl Fn(&Problem) -> Result<usize, String>) -> Answer {
+    let mut total = 0;
+    for (i, problem) in input.iter().enumerate() {
+        let count = match presses(problem) {
+            Ok(count) => count,
+            Err(reason) => return Answer::Unsolvable(format!("machine {} {}", i + 1, reason))
+        };
+        total += count;
+    }
+    total.into()
+}
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Input = Vec<Problem>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(input).map(parse_problem).collect()
+    }
+    fn part1(&self, input: &Self::Input) -> Answer {
+        total_presses(input, |problem| {
+            solve_lights(problem).map(|presses| presses.count).ok_or("can't reach its light goal".to_string())
+        })
+    }
+    fn part2(&self, input: &Self::Input) -> Answer {
+        total_presses(input, solve_joltage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem(text: &str) -> Result<Problem, ParseError> {
+        parse_problem(Line { number: 1, text })
+    }
+
+    #[test]
+    fn presses_light_the_goal() {
+        let problem = problem("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}").unwrap();
+        let presses = solve_lights(&problem).unwrap();
+        assert_eq!(presses.count, 2);
+        let lights = presses.buttons.iter().fold(vec![false; 4], |lights, &i| toggle(&lights, &problem.buttons[i]));
+        assert_eq!(lights, problem.light_goal);
+    }
+
+    #[test]
+    fn unreachable_lights_are_reported() {
+        let input = Day10.parse("[#..] (0,2) (1) (0) {1,1,1}\n[#..] (0,2) (1) {1,1,1}\n").unwrap();
+        assert_eq!(solve_lights(&input[0]).map(|presses| presses.count), Some(1));
+        assert!(solve_lights(&input[1]).is_none());
+        assert_eq!(Day10.part1(&input), Answer::Unsolvable("machine 2 can't reach its light goal".to_string()));
+        assert_eq!(Day10.part2(&input), Answer::Number(4));
+    }
+
+    #[test]
+    fn unreachable_joltages_are_reported() {
+        // Button (0,1) always adds the same to both counters.
+        let input = Day10.parse("[..] (0,1) {1,1}\n[..] (0,1) {1,2}\n").unwrap();
+        assert_eq!(Day10.part2(&input), Answer::Unsolvable("machine 2 can't reach its joltage goal".to_string()));
+    }
+}
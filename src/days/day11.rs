@@ -0,0 +1,58 @@
+use crate::graph::{Graph, PathCounter};
+use crate::parse::ParseError;
+use crate::solution::{Answer, Solution};
+
+pub struct Day11;
+
+// The number of paths between two named devices, or the reason there are
+// infinitely many.
+fn count_paths_by_name(counter: &mut PathCounter, graph: &Graph, src: &str, dst: &str) -> Result<u64, String> {
+    match (graph.index(src), graph.index(dst)) {
+        (Some(src_index), Some(dst_index)) => counter.count(src_index, dst_index).map_err(|error| {
+            format!("infinitely many paths from {} to {} as {} is on a cycle", src, dst, graph.name(error.key))
+        }),
+        _ => Ok(0)
+    }
+}
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Input = Graph;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Graph::from_adjacency(input)
+    }
+    fn part1(&self, graph: &Self::Input) -> Answer {
+        match count_paths_by_name(&mut PathCounter::new(graph), graph, "you", "out") {
+            Ok(count) => count.into(),
+            Err(reason) => Answer::Unsolvable(reason)
+        }
+    }
+    fn part2(&self, graph: &Self::Input) -> Answer {
+        let routes = [
+            ["svr", "dac", "fft", "out"],
+            ["svr", "fft", "dac", "out"],
+        ];
+        let mut counter = PathCounter::new(graph);
+        let counts = routes.iter().map(|route| {
+            route.windows(2).map(|w| count_paths_by_name(&mut counter, graph, w[0], w[1])).product::<Result<u64, String>>()
+        }).sum::<Result<u64, String>>();
+        match counts {
+            Ok(count) => count.into(),
+            Err(reason) => Answer::Unsolvable(reason)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_on_a_route_are_reported() {
+        let graph = Day11.parse("you: a\na: b out\nb: a\nsvr: fft dac\nfft: dac\ndac: out\n").unwrap();
+        let reason = "infinitely many paths from you to out as a is on a cycle".to_string();
+        assert_eq!(Day11.part1(&graph), Answer::Unsolvable(reason));
+        assert_eq!(Day11.part2(&graph), Answer::Number(1));
+    }
+}
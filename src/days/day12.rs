@@ -0,0 +1,335 @@
+use crate::util::{cartestian_product, Grid};
+use crate::parse::{self, Line, ParseError};
+use crate::solution::{Answer, Solution};
+
+pub struct Day12;
+
+trait ShapeView {
+    fn height(&self) -> usize;
+    fn width(&self) -> usize;
+    fn at(&self, coord: (usize, usize)) -> bool;
+    fn coords(&self) -> impl Iterator<Item=(usize, usize)> {
+        cartestian_product(0..self.width(), 0..self.height()).filter(|&coord| self.at(coord))
+    }
+    fn area(&self) -> usize {
+        self.coords().count()
+    }
+    fn rotate(&self, turns: usize) -> RotatedShape<'_, Self>{
+        RotatedShape { shape: self, turns }
+    }
+    fn translate(&self, offset: (usize, usize)) -> TranslatedShape<'_, Self>{
+        TranslatedShape { shape: self, offset }
+    }
+    fn flip(&self) -> FlippedShape<'_, Self>{
+        FlippedShape { shape: self }
+    }
+    fn as_shape(&self) -> Shape {
+        Shape{cells: Grid::from_fn(self.height(), self.width(), |point| self.at((point.col, point.row)))}
+    }
+}
+
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
+pub struct Shape {
+    cells: Grid<bool>
+}
+
+impl ShapeView for Shape {
+    fn width(&self) -> usize {
+        self.cells.cols()
+    }
+    fn height(&self) -> usize {
+        self.cells.rows()
+    }
+    fn at(&self, coord: (usize, usize)) -> bool {
+        let (x, y) = coord;
+        self.cells[(y, x)]
+    }
+}
+
+struct RotatedShape<'a, T: ShapeView + ?Sized> {
+    shape: &'a T,
+    turns: usize
+}
+
+impl<'a, T: ShapeView + ?Sized> ShapeView for RotatedShape<'a, T> {
+    fn height(&self) -> usize {
+        if self.turns.is_multiple_of(2) { self.shape.height() } else { self.shape.width() }
+    }
+    fn width(&self) -> usize {
+        if self.turns.is_multiple_of(2) { self.shape.width() } else { self.shape.height() }
+    }
+    fn at(&self, coord: (usize, usize)) -> bool {
+        let map_coord = |coord: (usize, usize)| {
+            let (x, y) = coord;
+            match self.turns % 4 {
+                0 => (x, y),
+                1 => (self.shape.width() - 1 - y, x),
+                2 => (self.shape.width() - 1 - x, self.shape.height() - 1 - y),
+                3 => (y, self.shape.height() - 1 - x),
+                _ => panic!()
+            }
+        };
+        self.shape.at(map_coord(coord))
+    }
+}
+
+struct FlippedShape<'a, T: ShapeView + ?Sized> {
+    shape: &'a T
+}
+
+impl<'a, T: ShapeView + ?Sized> ShapeView for FlippedShape<'a, T> {
+    fn height(&self) -> usize {
+        self.shape.height()
+    }
+    fn width(&self) -> usize {
+        self.shape.width()
+    }
+    fn at(&self, coord: (usize, usize)) -> bool {
+        let (x, y) = coord;
+        self.shape.at((self.shape.width() - 1 - x, y))
+    }
+}
+
+struct TranslatedShape<'a, T: ShapeView + ?Sized> {
+    shape: &'a T,
+    offset: (usize, usize)
+}
+
+impl<'a, T: ShapeView + ?Sized> ShapeView for TranslatedShape<'a, T> {
+    fn height(&self) -> usize {
+        self.shape.height()
+    }
+    fn width(&self) -> usize {
+        self.shape.width()
+    }
+    fn at(&self, coord: (usize, usize)) -> bool {
+        if coord.0 < self.offset.0 || coord.1 < self.offset.1 {
+            false
+        } else {
+            self.shape.at((coord.0 - self.offset.0, coord.1 - self.offset.1))
+        }
+    }
+    fn coords(&self) -> impl Iterator<Item=(usize, usize)> {
+        self.shape.coords().map(|(x, y)| (x + self.offset.0, y + self.offset.1))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Problem {
+    height: usize,
+    width: usize,
+    counts: Vec<usize>
+}
+
+impl Problem {
+    fn grid_size(&self) -> usize {
+        self.height * self.width
+    }
+}
+
+
+#[derive(Clone, Debug)]
+pub struct Input {
+    shapes: Vec<Shape>,
+    problems: Vec<Problem>
+}
+
+fn parse_problem(line: Line, num_shapes: usize) -> Result<Problem, ParseError> {
+    let (size, counts) = line.key_values()?;
+    let Some((width, height)) = size.split_once('x') else { return Err(line.error(size, "a region size such as '4x4'")); };
+    let width = line.parse::<usize>(width, "a width")?;
+    let height = line.parse::<usize>(height, "a height")?;
+    let counts = counts.into_iter().map(|s| {
+        line.parse::<usize>(s, "a shape count")
+    }).collect::<Result<Vec<usize>, ParseError>>()?;
+    if counts.len() != num_shapes {
+        return Err(line.error_at_end(format!("{} shape counts", num_shapes)));
+    }
+    Ok(Problem{height, width, counts})
+}
+
+fn parse_shape(section: &[Line]) -> Result<Shape, ParseError> {
+    let header = section[0];
+    let (index, rest) = header.key_values()?;
+    header.parse::<usize>(index, "a shape index")?;
+    if let Some(rest) = rest.first() {
+        return Err(header.error(rest, "end of line"));
+    }
+    if section.len() == 1 {
+        return Err(header.error_at_end("shape rows after the header"));
+    }
+    let cells = Grid::parse_lines(&section[1..], |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None
+    }, "'#' or '.'")?;
+    Ok(Shape{cells})
+}
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    // Each shape is a section introduced by an index header such as "0:". The
+    // regions, such as "4x4: 0 0 0 0 2 0", make up the final section.
+    let sections = parse::sections(input);
+    let Some((regions, shapes)) = sections.split_last() else {
+        return Err(parse::end_of_input(input, "a region such as '4x4: 0 0 0 0 2 0'"));
+    };
+    let shapes = shapes.iter().map(|section| parse_shape(section)).collect::<Result<Vec<Shape>, ParseError>>()?;
+    let problems = regions.iter().map(|&line| parse_problem(line, shapes.len())).collect::<Result<Vec<Problem>, ParseError>>()?;
+    Ok(Input { shapes, problems })
+}
+
+fn get_tranformations(shape: &Shape) -> Vec<Shape> {
+    let mut transformed: Vec<Shape> = ((0..4).map(|turns| shape.rotate(turns)).flat_map(|shape| {
+        [shape.as_shape(), shape.flip().as_shape()]
+    })).collect();
+    transformed.sort();
+    transformed.dedup();
+    transformed
+}
+
+// Shapes use (x, y) coordinates while the grid is indexed by (row, col), so
+// coordinates are looked up as grid[(y, x)].
+fn is_filled(grid: &Grid<bool>, coord: (usize, usize)) -> bool {
+    let (x, y) = coord;
+    grid[(y, x)]
+}
+
+fn set_filled(grid: &mut Grid<bool>, coord: (usize, usize), filled: bool) {
+    let (x, y) = coord;
+    grid[(y, x)] = filled;
+}
+
+fn get_next_coord(grid: &Grid<bool>, coord: (usize, usize)) -> Option<(usize, usize)> {
+    let (mut x, mut y) = coord;
+    loop {
+        if x + 1 < grid.cols() {
+            x += 1;
+        } else if y + 1 < grid.rows() {
+            x = 0;
+            y += 1;
+        } else {
+            return None;
+        }
+        if !is_filled(grid, (x, y)) {
+            return Some((x, y));
+        }
+    }
+}
+
+fn solve_grid(grid: &mut Grid<bool>, shapes: &Vec<(Shape, usize)>, coord: (usize, usize), remaining: &mut Vec<usize>) -> bool {
+    let (x, y) = coord;
+    // Try every possible combination of shape and offset to fill the coord.
+    for (shape, i) in shapes {
+        if remaining[*i] == 0 || shape.width() > grid.cols() || shape.height() > grid.rows() {
+            continue;
+        }
+        let x_start = if x < shape.width() { 0usize } else { x + 1 - shape.width() };
+        let x_end = x.min(grid.cols() - shape.width()) + 1;
+        let y_start = if y < shape.height() { 0usize } else { y + 1 - shape.height() };
+        let y_end = y.min(grid.rows() - shape.height()) + 1;
+        for x_offset in x_start..x_end {
+            for y_offset in y_start..y_end {
+                let translated_shape = shape.translate((x_offset, y_offset));
+                if translated_shape.coords().any(|coord| is_filled(grid, coord)) {
+                    // Shape cannot be placed.
+                    continue;
+                }
+                // Place the shape and recusively fill the remaining coordintates. If
+                // there are no remaining coordinates we are done.
+                translated_shape.coords().for_each(|coord| set_filled(grid, coord, true));
+                let Some(next_coord) = get_next_coord(grid, coord) else { return true; };
+                remaining[*i] -= 1;
+                if solve_grid(grid, shapes, next_coord, remaining) {
+                    return true;
+                }
+                // Otherwise undo the change and carry on searching.
+                remaining[*i] += 1;
+                translated_shape.coords().for_each(|coord| set_filled(grid, coord, false));
+            }
+        }
+    }
+    // If we get here the coordinate cannot be filled.
+    false
+}
+
+fn solve(shapes: &Vec<(Shape, usize)>, problem: &Problem) -> bool {
+    // The region has `height` rows and `width` columns, but is searched
+    // transposed so that it fills down the columns first. Every orientation of
+    // each present is tried so the answer is the same, and the search is far
+    // quicker on regions that are wider than they are tall.
+    let mut grid = Grid::new(problem.height, problem.width, false).transpose();
+    let mut remaining = problem.counts.clone();
+    solve_grid(&mut grid, shapes, (0, 0), &mut remaining)
+}
+
+fn solve_input(input: &Input) -> usize {
+    // Compute all possible transformations and add an extra 1x1 shape at the end.
+    let mut expanded_shapes: Vec<(Shape, usize)> = input.shapes.iter().enumerate().flat_map(|(i, shape)| {
+        get_tranformations(shape).into_iter().map(move |shape| (shape, i))
+    }).collect();
+    expanded_shapes.push((Shape{cells: Grid::new(1, 1, true)}, input.shapes.len()));
+    // Turn each problem into an exact cover problem by including the number of 1x1 shapes
+    // required to completely fill the grid.
+    let shape_areas: Vec<usize> = input.shapes.iter().map(|shape| shape.area()).collect();
+    let problems: Vec<Problem> = input.problems.iter().flat_map(|problem| {
+        let used_area: usize = problem.counts.iter().zip(shape_areas.iter()).map(|(&count, area)| count * area).sum();
+        let available_area = problem.grid_size();
+        if used_area > available_area {
+            None
+        } else {
+            let unused_area = available_area - used_area;
+            let mut problem = problem.clone();
+            problem.counts.push(unused_area);
+            Some(problem)
+        }
+    }).collect();
+    // Solve.
+    problems.iter().filter(|problem| solve(&expanded_shapes, problem)).count()
+}
+
+fn place_by_brute_force(grid: &mut Grid<bool>, presents: &[Vec<Shape>]) -> bool {
+    let Some((orientations, rest)) = presents.split_first() else { return true; };
+    let (width, height) = (grid.cols(), grid.rows());
+    for shape in orientations {
+        if shape.width() > width || shape.height() > height {
+            continue;
+        }
+        for offset in cartestian_product(0..width - shape.width() + 1, 0..height - shape.height() + 1) {
+            let coords: Vec<(usize, usize)> = shape.translate(offset).coords().collect();
+            if coords.iter().any(|&coord| is_filled(grid, coord)) {
+                continue;
+            }
+            coords.iter().for_each(|&coord| set_filled(grid, coord, true));
+            if place_by_brute_force(grid, rest) {
+                return true;
+            }
+            coords.iter().for_each(|&coord| set_filled(grid, coord, false));
+        }
+    }
+    false
+}
+
+// Slow reference for solve_input which places the presents one at a time in
+// every orientation and position, without padding the region with 1x1 shapes.
+pub fn count_fitting_by_brute_force(input: &Input) -> usize {
+    let orientations: Vec<Vec<Shape>> = input.shapes.iter().map(get_tranformations).collect();
+    input.problems.iter().filter(|problem| {
+        let presents: Vec<Vec<Shape>> = problem.counts.iter().enumerate().flat_map(|(i, &count)| {
+            std::iter::repeat_n(orientations[i].clone(), count)
+        }).collect();
+        let mut grid = Grid::new(problem.height, problem.width, false);
+        place_by_brute_force(&mut grid, &presents)
+    }).count()
+}
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    type Input = Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(&self, input: &Self::Input) -> Answer {
+        solve_input(input).into()
+    }
+}
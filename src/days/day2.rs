@@ -0,0 +1,122 @@
+use crate::parse::{self, Line, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::util::IntervalSet;
+use crate::util::number::{is_repeated, num_digits};
+use std::ops::RangeInclusive;
+
+pub struct Day2;
+
+fn invalid(id: u64) -> bool {
+    (2..num_digits(id, 10) + 1).any(|n| is_repeated(id, n))
+}
+
+// The input with its line breaks removed, as a range may be wrapped part way
+// through. Where each line starts is kept so errors point back into it.
+struct Unwrapped<'a> {
+    lines: Vec<Line<'a>>,
+    starts: Vec<usize>,
+    text: String
+}
+
+impl<'a> Unwrapped<'a> {
+    fn new(input: &'a str) -> Self {
+        let lines: Vec<Line> = parse::lines(input).collect();
+        let mut starts = Vec::new();
+        let mut text = String::new();
+        for line in &lines {
+            starts.push(text.len());
+            text.push_str(line.text);
+        }
+        Unwrapped { lines, starts, text }
+    }
+
+    // An error at `part` of the joined text, reported on the line it starts
+    // on.
+    fn error(&self, part: &str, expected: &str) -> ParseError {
+        let offset = part.as_ptr() as usize - self.text.as_ptr() as usize;
+        let i = self.starts.partition_point(|&start| start <= offset) - 1;
+        let line = self.lines[i];
+        let from = offset - self.starts[i];
+        line.error(&line.text[from..from + part.len().min(line.text.len() - from)], expected)
+    }
+
+    fn parse(&self, part: &str, expected: &str) -> Result<u64, ParseError> {
+        part.parse::<u64>().map_err(|_| self.error(part, expected))
+    }
+}
+
+fn ids_in_range(input: &Unwrapped, range: &str) -> Result<RangeInclusive<u64>, ParseError> {
+    let Some((low, high)) = range.split_once('-') else { return Err(input.error(range, "a range such as '11-22'")); };
+    Ok(input.parse(low, "an ID")?..=input.parse(high, "an ID")?)
+}
+
+fn all_ids(ranges: &IntervalSet<u64>) -> impl Iterator<Item=u64> + '_ {
+    ranges.iter().flat_map(|range| range.clone())
+}
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    type Input = IntervalSet<u64>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        // The ranges are comma separated and may be wrapped over several lines.
+        let input = Unwrapped::new(input);
+        let mut ids = IntervalSet::new();
+        for range in input.text.split(',').filter(|range| !range.is_empty()) {
+            ids.insert_inclusive(ids_in_range(&input, range)?).ok_or_else(|| input.error(range, "a range ending below 2^64 - 1"))?;
+        }
+        Ok(ids)
+    }
+    fn part1(&self, input: &Self::Input) -> Answer {
+        all_ids(input).filter(|&id| is_repeated(id, 2)).sum::<u64>().into()
+    }
+    fn part2(&self, input: &Self::Input) -> Answer {
+        all_ids(input).filter(|id| invalid(*id)).sum::<u64>().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_matches() {
+        assert!(is_repeated(1212, 2));
+        assert!(is_repeated(123123123, 3));
+        assert!(is_repeated(111, 3));
+        assert!(is_repeated(7, 1));
+    }
+
+    #[test]
+    fn repeated_mismatches() {
+        assert!(!is_repeated(1213, 2));
+        assert!(!is_repeated(123123, 3));
+        assert!(!is_repeated(12312, 2));
+    }
+
+    #[test]
+    fn invalid_ids() {
+        assert!(invalid(11));
+        assert!(invalid(111));
+        assert!(invalid(1188511885));
+        assert!(!invalid(7));
+        assert!(!invalid(1010101));
+    }
+
+    #[test]
+    fn ranges_are_inclusive() {
+        let ranges = Day2.parse("11-22,95-115").unwrap();
+        assert_eq!(ranges.iter().cloned().collect::<Vec<_>>(), vec![11..23, 95..116]);
+    }
+
+    #[test]
+    fn ranges_wrap_across_lines() {
+        let ranges = Day2.parse("11-2\n2,95\n-115,\n").unwrap();
+        assert_eq!(ranges.iter().cloned().collect::<Vec<_>>(), vec![11..23, 95..116]);
+        // A part wrapped over lines is reported from where it starts.
+        let error = Day2.parse("11-22,9x\n5-15").unwrap_err();
+        assert_eq!((error.line, error.column, error.found.as_str()), (1, 7, "'9x'"));
+        let error = Day2.parse("11-22,95-\n1x5").unwrap_err();
+        assert_eq!((error.line, error.column, error.found.as_str()), (2, 1, "'1x5'"));
+    }
+}
@@ -0,0 +1,47 @@
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::util::number::from_digits;
+
+pub struct Day3;
+
+
+pub fn sum_of_topk_in_seq(numbers: &[u32], k: usize) -> u64 {
+    let n = numbers.len();
+    let top = (n-k..n).scan(0, |acc, last| {
+        let first = *acc;
+        let indices = first..last+1;
+        // If there are multiple highest max_by_key takes the last, so reverse the range to get the first.
+        let highest_idx = indices.rev().max_by_key(|i| numbers[*i]).unwrap();
+        *acc = highest_idx + 1;
+        Some(numbers[highest_idx])
+    });
+    from_digits(top, 10)
+}
+
+// The total joltage with `k` batteries on in every bank, or the first bank
+// with fewer than that.
+fn total_joltage(banks: &[Vec<u32>], k: usize) -> Answer {
+    if let Some(i) = banks.iter().position(|bank| bank.len() < k) {
+        return Answer::Unsolvable(format!("bank {} has fewer than {} batteries", i + 1, k));
+    }
+    banks.iter().map(|bank| sum_of_topk_in_seq(bank, k)).sum::<u64>().into()
+}
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+    type Input = Vec<Vec<u32>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(input).map(|line| {
+            line.text.char_indices().map(|(i, c)| {
+                c.to_digit(10).ok_or_else(|| line.error(line.char_at(i), "a battery joltage digit"))
+            }).collect()
+        }).collect()
+    }
+    fn part1(&self, input: &Self::Input) -> Answer {
+        total_joltage(input, 2)
+    }
+    fn part2(&self, input: &Self::Input) -> Answer {
+        total_joltage(input, 12)
+    }
+}
@@ -0,0 +1,54 @@
+use crate::parse::ParseError;
+use crate::solution::{Answer, Solution};
+use crate::util::{Grid, Point};
+use std::collections::HashSet;
+
+pub struct Day4;
+
+fn rolls(grid: &Grid<bool>) -> impl Iterator<Item=Point> + '_ {
+    grid.points().filter(|&point| grid[point])
+}
+
+fn neighbouring_rolls(grid: &Grid<bool>, point: Point) -> impl Iterator<Item=Point> + '_ {
+    grid.neighbours8(point).filter(|&point| grid[point])
+}
+
+fn is_accessible(grid: &Grid<bool>, point: Point) -> bool {
+    neighbouring_rolls(grid, point).count() < 4
+}
+
+fn remove_accessible(grid: &mut Grid<bool>) {
+    let mut worklist = HashSet::new();
+    worklist.extend(rolls(grid));
+    while !worklist.is_empty() {
+        let point = *worklist.iter().next().unwrap();
+        worklist.remove(&point);
+        if is_accessible(grid, point) {
+            grid[point] = false;
+            worklist.extend(neighbouring_rolls(grid, point));
+        }
+    }
+}
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    type Input = Grid<bool>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input, |c| match c {
+            '@' => Some(true),
+            '.' => Some(false),
+            _ => None
+        }, "'@' or '.'")
+    }
+    fn part1(&self, grid: &Self::Input) -> Answer {
+        rolls(grid).filter(|&point| is_accessible(grid, point)).count().into()
+    }
+    fn part2(&self, grid: &Self::Input) -> Answer {
+        let mut grid = grid.clone();
+        let starting_rolls = rolls(&grid).count();
+        remove_accessible(&mut grid);
+        let ending_rolls = rolls(&grid).count();
+        (starting_rolls - ending_rolls).into()
+    }
+}
@@ -0,0 +1,77 @@
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::util::IntervalSet;
+
+pub struct Day5;
+
+pub struct Database {
+    fresh: IntervalSet<u64>,
+    ids: Vec<u64>
+}
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+    type Input = Database;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let sections = parse::sections(input);
+        let [ranges, ids] = &sections[..] else { return Err(parse::end_of_input(input, "ranges and IDs separated by a blank line")); };
+        let mut fresh = IntervalSet::new();
+        for line in ranges {
+            let range = line.list(line.text, "-", |id| line.parse::<u64>(id, "an ID"))?;
+            let [low, high] = range[..] else { return Err(line.error(line.text, "a range such as '3-5'")); };
+            fresh.insert_inclusive(low..=high).ok_or_else(|| line.error(line.text, "a range ending below 2^64 - 1"))?;
+        }
+        let ids = ids.iter().map(|line| {
+            line.parse::<u64>(line.text, "an ingredient ID")
+        }).collect::<Result<Vec<u64>, ParseError>>()?;
+        Ok(Database { fresh, ids })
+    }
+    fn part1(&self, input: &Self::Input) -> Answer {
+        input.ids.iter().filter(|&&id| input.fresh.contains(id)).count().into()
+    }
+    fn part2(&self, input: &Self::Input) -> Answer {
+        input.fresh.total_length().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fresh_ranges(input: &str) -> Vec<(u64, u64)> {
+        Day5.parse(input).unwrap().fresh.iter_inclusive().collect()
+    }
+
+    #[test]
+    fn fresh_ranges_merge_overlapping() {
+        assert_eq!(fresh_ranges("10-14\n12-18\n16-20\n\n1\n"), vec![(10, 20)]);
+    }
+
+    #[test]
+    fn fresh_ranges_merge_contained_and_touching() {
+        assert_eq!(fresh_ranges("1-10\n3-5\n\n1\n"), vec![(1, 10)]);
+        assert_eq!(fresh_ranges("3-5\n5-8\n\n1\n"), vec![(3, 8)]);
+        assert_eq!(fresh_ranges("3-5\n6-8\n\n1\n"), vec![(3, 8)]);
+    }
+
+    #[test]
+    fn fresh_ranges_keep_disjoint_sorted() {
+        assert_eq!(fresh_ranges("16-20\n3-5\n10-14\n\n1\n"), vec![(3, 5), (10, 14), (16, 20)]);
+        assert_eq!(fresh_ranges("3-5\n7-8\n\n1\n"), vec![(3, 5), (7, 8)]);
+    }
+
+    #[test]
+    fn ranges_ending_at_the_largest_id_are_rejected() {
+        let error = Day5.parse("3-5\n1-18446744073709551615\n\n1\n").err().unwrap();
+        assert_eq!((error.line, error.expected.as_str()), (2, "a range ending below 2^64 - 1"));
+        assert_eq!(fresh_ranges("1-18446744073709551614\n\n1\n"), vec![(1, u64::MAX - 1)]);
+    }
+
+    #[test]
+    fn ranges_are_inclusive() {
+        let database = Day5.parse("3-5\n\n2\n3\n5\n6\n").unwrap();
+        assert_eq!(Day5.part1(&database), Answer::Number(2));
+        assert_eq!(Day5.part2(&database), Answer::Number(3));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::util::number::from_digits;
+use std::iter;
+
+pub struct Day6;
+
+pub struct Worksheet {
+    rows: Vec<String>,
+    // The numbers in each row read left to right.
+    numbers: Vec<Vec<u64>>,
+    operators: Vec<fn(u64, u64) -> u64>
+}
+
+fn read_human_numbers(rows: &[Vec<u64>]) -> Vec<Vec<u64>> {
+    (0..rows[0].len()).map(|i| rows.iter().map(|row| row[i]).collect()).collect()
+}
+
+fn read_cephalopod_numbers(lines: &[String]) -> Vec<Vec<u64>> {
+    // Each column's digits read top to bottom form a number, skipping spaces.
+    let cols: Vec<u64> = (0..lines[0].len()).map(|i| {
+        from_digits(lines.iter().filter_map(|line| (*line.as_bytes().get(i)? as char).to_digit(10)), 10)
+    }).collect();
+    // Problems are separated by columns of spaces. A column of zeros is still
+    // a number.
+    let empty_cols: Vec<usize> = (0..cols.len()).filter(|&i| {
+        lines.iter().all(|line| line.as_bytes().get(i).is_none_or(|&byte| byte == b' '))
+    }).collect();
+    let starts = iter::once(0usize).chain(empty_cols.iter().map(|i| i + 1));
+    let ends = empty_cols.iter().cloned().chain(iter::once(cols.len()));
+    starts.zip(ends).map(|(start, end)| {
+        cols[start..end].to_vec()
+    }).collect()
+}
+
+fn parse_operator(field: &str) -> Option<fn(u64, u64) -> u64> {
+    match field {
+        "*" => Some(|a, b| a * b),
+        "+" => Some(|a, b| a + b),
+        _ => None
+    }
+}
+
+fn apply_operators(numbers: &[Vec<u64>], operators: &[fn(u64, u64) -> u64]) -> u64 {
+    numbers.iter().zip(operators.iter()).map(|(col, op)| {
+        col.iter().cloned().reduce(op).unwrap()
+    }).sum()
+}
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    type Input = Worksheet;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let mut lines: Vec<_> = parse::lines(input).collect();
+        let Some(operator_line) = lines.pop() else { return Err(parse::end_of_input(input, "a row of operators")); };
+        let operators = operator_line.text.split_whitespace().map(|field: &str| {
+            parse_operator(field).ok_or_else(|| operator_line.error(field, "'*' or '+'"))
+        }).collect::<Result<Vec<fn(u64, u64) -> u64>, ParseError>>()?;
+        if lines.is_empty() {
+            return Err(operator_line.error(operator_line.text, "a row of numbers"));
+        }
+        for line in &lines {
+            if let Some(i) = line.text.find(|c: char| !c.is_ascii_digit() && c != ' ') {
+                return Err(line.error(line.char_at(i), "a digit or space"));
+            }
+            let num_fields = line.text.split_whitespace().count();
+            if num_fields != operators.len() {
+                return Err(line.error_at_end(format!("{} numbers", operators.len())));
+            }
+        }
+        let numbers = lines.iter().map(|line| {
+            line.text.split_whitespace().map(|field| line.parse::<u64>(field, "a number below 2^64")).collect()
+        }).collect::<Result<Vec<Vec<u64>>, ParseError>>()?;
+        let rows = lines.iter().map(|line| line.text.to_string()).collect();
+        Ok(Worksheet { rows, numbers, operators })
+    }
+    fn part1(&self, input: &Self::Input) -> Answer {
+        apply_operators(&read_human_numbers(&input.numbers), &input.operators).into()
+    }
+    fn part2(&self, input: &Self::Input) -> Answer {
+        apply_operators(&read_cephalopod_numbers(&input.rows), &input.operators).into()
+    }
+}
@@ -0,0 +1,85 @@
+use crate::parse::ParseError;
+use crate::solution::{Answer, Solution};
+use crate::util::{Grid, Point};
+
+pub struct Day7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Start,
+    Splitter
+}
+
+pub fn count_splits(grid: &Grid<Cell>) -> u64 {
+    let beams : Vec<bool> = grid.row(0).iter().map(|&c| c == Cell::Start).collect();
+    let (splits, _) =
+        grid.iter_rows().skip(1).fold((0, beams), |acc, row| {
+            let (mut total, beams) = acc;
+            let splits : Vec<bool> = row.iter().zip(beams.iter()).map(|(&c, &b)| {
+                b && c == Cell::Splitter
+            }).collect();
+            total += splits.iter().copied().filter(|&b| b).count() as u64;
+            let next: Vec<bool> = (0..row.len()).map(|i| {
+                beams[i] && !splits[i] ||
+                (i > 0 && splits[i-1]) ||
+                (i + 1 < splits.len() && splits[i+1])
+            }).collect();
+            (total, next)
+        });
+    splits
+}
+
+pub fn count_timelines(grid: &Grid<Cell>) -> u64 {
+    let beams : Vec<u64> = grid.row(0).iter().map(|&c| (c == Cell::Start) as u64).collect();
+    let timelines =
+        grid.iter_rows().skip(1).fold(beams, |beams, row| {
+            let splits : Vec<u64> = row.iter().zip(beams.iter()).map(|(&c, &t)| {
+                if c == Cell::Splitter { t } else { 0 }
+            }).collect();
+            (0..row.len()).map(|i| {
+                (if splits[i] == 0 { beams[i] } else { 0 }) +
+                (if i > 0 { splits[i-1] } else { 0 }) +
+                (if i + 1 < splits.len() { splits[i+1] } else { 0 })
+            }).collect()
+        });
+    timelines.iter().sum()
+}
+
+// Slow reference for count_timelines which follows every timeline separately.
+pub fn count_timelines_by_enumeration(grid: &Grid<Cell>) -> u64 {
+    fn follow(grid: &Grid<Cell>, point: Point) -> u64 {
+        let Some(&cell) = grid.get(point) else { return 1; };
+        let below = |col| Point::new(point.row + 1, col);
+        if cell != Cell::Splitter {
+            return follow(grid, below(point.col));
+        }
+        let left = point.col.checked_sub(1).map_or(0, |left| follow(grid, below(left)));
+        let right = if point.col + 1 < grid.cols() { follow(grid, below(point.col + 1)) } else { 0 };
+        left + right
+    }
+    grid.iter().filter(|&(point, &cell)| point.row == 0 && cell == Cell::Start).map(|(point, _)| {
+        follow(grid, Point::new(1, point.col))
+    }).sum()
+}
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+    type Input = Grid<Cell>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        // Beams may already be drawn in with '|', these are treated as empty space.
+        Grid::parse(input, |c| match c {
+            '.' | '|' => Some(Cell::Empty),
+            'S' => Some(Cell::Start),
+            '^' => Some(Cell::Splitter),
+            _ => None
+        }, "one of '.', 'S', '^' or '|'")
+    }
+    fn part1(&self, input: &Self::Input) -> Answer {
+        count_splits(input).into()
+    }
+    fn part2(&self, input: &Self::Input) -> Answer {
+        count_timelines(input).into()
+    }
+}
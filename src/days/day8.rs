@@ -0,0 +1,89 @@
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::util::{DisjointSet, Point3};
+
+pub struct Day8;
+
+// The puzzle connects the 1000 closest pairs for the real input but only the
+// 10 closest for the example.
+const NUM_CONNECTIONS: usize = 1000;
+const EXAMPLE_NUM_CONNECTIONS: usize = 10;
+
+pub struct Playground {
+    coords: Vec<Point3<u64>>,
+    // Every pair of boxes with their squared distance, closest first.
+    distances: Vec<(usize, usize, u64)>,
+    num_connections: usize
+}
+
+fn part1(distances: &[(usize, usize, u64)], n: usize, num_connections: usize) -> usize {
+    let mut circuits = DisjointSet::new(n);
+    let direct_connections = distances.iter().take(num_connections).map(|&(i, j, _)| (i, j));
+    direct_connections.for_each(|(i, j)| {
+        circuits.union(i, j);
+    });
+    // Count the boxes absorbed into other circuits as empty circuits, so a
+    // playground with fewer than three circuits scores zero.
+    let mut circuit_sizes = circuits.component_sizes();
+    circuit_sizes.resize(n, 0);
+    circuit_sizes.sort();
+    circuit_sizes[n-3..].iter().product()
+}
+
+fn part2(coords: &[Point3<u64>], distances: &[(usize, usize, u64)], n: usize) -> Answer {
+    let mut circuits = DisjointSet::new(n);
+    let mut it = distances.iter();
+    let mut last: Option<(usize, usize)> = None;
+    while circuits.num_components() > 1 {
+        let Some(&(i, j, _)) = it.next() else {
+            return Answer::Unsolvable("the junction boxes never form a single circuit".to_string());
+        };
+        circuits.union(i, j);
+        last = Some((i, j))
+    }
+    let Some((i, j)) = last else {
+        return Answer::Unsolvable("there is only one junction box".to_string());
+    };
+    (coords[i].x * coords[j].x).into()
+}
+
+fn parse_playground(input: &str, num_connections: usize) -> Result<Playground, ParseError> {
+    let coords = parse::lines(input).map(|line| {
+        let parts: Vec<&str> = line.text.split(',').collect();
+        let [x, y, z] = parts[..] else { return Err(line.error(line.text, "three comma separated coordinates")); };
+        Ok(Point3::new(
+            line.parse::<u32>(x, "an X coordinate")? as u64,
+            line.parse::<u32>(y, "a Y coordinate")? as u64,
+            line.parse::<u32>(z, "a Z coordinate")? as u64
+        ))
+    }).collect::<Result<Vec<Point3<u64>>, ParseError>>()?;
+    let n = coords.len();
+    // Part 1 multiplies the three largest circuits.
+    if n < 3 {
+        return Err(parse::end_of_input(input, "at least 3 junction boxes"));
+    }
+    let pairs = (0..n).flat_map(|j| (0..j).map(move |i| (i, j)));
+    let mut distances: Vec<(usize, usize, u64)> = pairs.map(|(i, j)| {
+        (i, j, coords[i].squared_distance(coords[j]))
+    }).collect();
+    distances.sort_by_key(|&(_, _, distance)| distance);
+    Ok(Playground { coords, distances, num_connections })
+}
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    type Input = Playground;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_playground(input, NUM_CONNECTIONS)
+    }
+    fn parse_example(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_playground(input, EXAMPLE_NUM_CONNECTIONS)
+    }
+    fn part1(&self, input: &Self::Input) -> Answer {
+        part1(&input.distances, input.coords.len(), input.num_connections).into()
+    }
+    fn part2(&self, input: &Self::Input) -> Answer {
+        part2(&input.coords, &input.distances, input.coords.len())
+    }
+}
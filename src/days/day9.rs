@@ -0,0 +1,203 @@
+use crate::parse::{self, ParseError};
+use crate::solution::{Answer, Solution};
+use crate::util::{Point2, PolygonError, RectilinearPolygon};
+use std::ops;
+
+pub struct Day9;
+
+// An axis aligned edge: its position and the range of points it covers.
+type Edge = (u32, ops::Range::<u32>);
+
+fn inclusive_points_between(a: u32, b: u32) -> ops::Range::<u32> {
+    if a <= b { a..b+1 } else { b..a+1 }
+}
+
+fn calc_area(a: Point2<u32>, b: Point2<u32>) -> u64 {
+    (a.x.abs_diff(b.x) as u64 + 1) * (a.y.abs_diff(b.y) as u64 + 1)
+}
+
+// Whether every tile of a line lies on or inside the polygon, given the
+// edges parallel and perpendicular to it. Following a ray from a tile along
+// the line, a tile is inside when the ray crosses an odd number of
+// perpendicular edges, counting an edge's lower end but not its upper one.
+// The tiles inside are therefore those from the first crossing edge to the
+// second, the third to the fourth and so on. Tiles on the boundary count too,
+// which covers edges that only touch the line or lie along it.
+pub fn line_is_inside(line: (u32, ops::Range::<u32>), parallel_edges: &[Edge], perpendicular_edges: &[Edge]) -> bool {
+    let (y, xs) = line;
+    let crossings: Vec<u32> = perpendicular_edges.iter().filter(|(_, ys)| ys.start <= y && y + 1 < ys.end).map(|&(x, _)| x).collect();
+    let mut covered: Vec<ops::Range<u32>> = crossings.chunks_exact(2).map(|pair| pair[0]..pair[1] + 1).collect();
+    covered.extend(perpendicular_edges.iter().filter(|(_, ys)| ys.contains(&y)).map(|&(x, _)| x..x + 1));
+    covered.extend(parallel_edges.iter().filter(|(edge_y, _)| *edge_y == y).map(|(_, xs)| xs.clone()));
+    covered.sort_by_key(|range| range.start);
+    // Walk the covered ranges from the left looking for a gap in the line.
+    let mut end = xs.start;
+    for range in covered {
+        if range.start > end || end >= xs.end {
+            break;
+        }
+        end = end.max(range.end);
+    }
+    end >= xs.end
+}
+
+fn is_inside(a: Point2<u32>, b: Point2<u32>, horizonal_edges: &[Edge], vertical_edges: &[Edge]) -> bool {
+    let Point2 { x: from_x, y: from_y } = a;
+    let Point2 { x: to_x, y: to_y } = b;
+    // Since the enclosed shape has no holes, the rectangle is contained inside
+    // the shape if and only if all the lines which make up the perimeter of
+    // the rectange lie inside the shape.
+    line_is_inside((from_y, inclusive_points_between(from_x, to_x)), horizonal_edges, vertical_edges) &&
+    line_is_inside((to_y, inclusive_points_between(from_x, to_x)), horizonal_edges, vertical_edges) &&
+    line_is_inside((from_x, inclusive_points_between(from_y, to_y)), vertical_edges, horizonal_edges) &&
+    line_is_inside((to_x, inclusive_points_between(from_y, to_y)), vertical_edges, horizonal_edges)
+}
+
+fn max_area(polygon: &RectilinearPolygon<u32>) -> u64 {
+    let coords = polygon.vertices();
+    let n = coords.len();
+    let pairs = (0..n).flat_map(|j| (0..j).map(move |i| (i, j)));
+    pairs.map(|(i, j)| calc_area(coords[i], coords[j])).max().unwrap()
+}
+
+fn max_inclosed_area(polygon: &RectilinearPolygon<u32>) -> u64 {
+    let coords = polygon.vertices();
+    let n = coords.len();
+    let pairs = (0..n).flat_map(|j| (0..j).map(move |i| (i, j)));
+    let mut areas: Vec<(u64, usize, usize)> = pairs.map(|(i, j)| {
+        (calc_area(coords[i], coords[j]), i, j)
+    }).collect();
+    areas.sort();
+    let (horizonal_edges, vertical_edges) = polygon_edges(polygon);
+    let mut max_inclosed_area = None;
+    for &(area, a, b) in areas.iter().rev() {
+        if is_inside(coords[a], coords[b], &horizonal_edges, &vertical_edges) {
+            max_inclosed_area = Some(area);
+            break;
+        }
+    }
+    max_inclosed_area.unwrap()
+}
+
+// Returns the horizontal and vertical edges of the polygon, each sorted by
+// position.
+pub fn polygon_edges(polygon: &RectilinearPolygon<u32>) -> (Vec<Edge>, Vec<Edge>) {
+    let half_open = |(position, range): (u32, ops::RangeInclusive<u32>)| (position, *range.start()..*range.end() + 1);
+    let mut horizonal_edges: Vec<Edge> = polygon.horizontal_edges().map(half_open).collect();
+    let mut vertical_edges: Vec<Edge> = polygon.vertical_edges().map(half_open).collect();
+    horizonal_edges.sort_by_key(|x| (x.0, x.1.start));
+    vertical_edges.sort_by_key(|x| (x.0, x.1.start));
+    (horizonal_edges, vertical_edges)
+}
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+    type Input = RectilinearPolygon<u32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let lines: Vec<parse::Line> = parse::lines(input).collect();
+        let coords = lines.iter().map(|line| {
+            let Some((x, y)) = line.text.split_once(',') else { return Err(line.error(line.text, "an 'x,y' coordinate")); };
+            Ok(Point2::new(line.parse::<u32>(x, "an X coordinate")?, line.parse::<u32>(y, "a Y coordinate")?))
+        }).collect::<Result<Vec<Point2<u32>>, ParseError>>()?;
+        RectilinearPolygon::new(coords.clone()).map_err(|error| match error {
+            PolygonError::TooFewVertices(_) => parse::end_of_input(input, "at least four red tiles"),
+            PolygonError::NotRectilinear(i) => {
+                let line = lines[(i + 1) % lines.len()];
+                line.error(line.text, format!("a tile in the same row or column as {}", coords[i]))
+            }
+        })
+    }
+    fn part1(&self, input: &Self::Input) -> Answer {
+        max_area(input).into()
+    }
+    fn part2(&self, input: &Self::Input) -> Answer {
+        max_inclosed_area(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example polygon:
+    //
+    //   .......#XXX#
+    //   .......XXXXX
+    //   ..#XXXX#XXXX
+    //   ..XXXXXXXXXX
+    //   ..#XXXXXX#XX
+    //   .........XXX
+    //   .........#X#
+    fn example() -> RectilinearPolygon<u32> {
+        let coords = [(7,1), (11,1), (11,7), (9,7), (9,5), (2,5), (2,3), (7,3)];
+        RectilinearPolygon::new(coords.into_iter().map(Point2::from).collect()).unwrap()
+    }
+
+    fn row_is_inside(y: u32, xs: ops::RangeInclusive<u32>) -> bool {
+        let (horizonal_edges, vertical_edges) = polygon_edges(&example());
+        line_is_inside((y, *xs.start()..*xs.end() + 1), &horizonal_edges, &vertical_edges)
+    }
+
+    fn column_is_inside(x: u32, ys: ops::RangeInclusive<u32>) -> bool {
+        let (horizonal_edges, vertical_edges) = polygon_edges(&example());
+        line_is_inside((x, *ys.start()..*ys.end() + 1), &vertical_edges, &horizonal_edges)
+    }
+
+    #[test]
+    fn line_along_boundary_is_inside() {
+        assert!(row_is_inside(1, 7..=11));
+        assert!(row_is_inside(5, 2..=9));
+        assert!(row_is_inside(7, 9..=11));
+        assert!(column_is_inside(11, 1..=7));
+        assert!(column_is_inside(2, 3..=5));
+    }
+
+    #[test]
+    fn line_through_interior_is_inside() {
+        assert!(row_is_inside(3, 2..=11));
+        assert!(row_is_inside(4, 2..=11));
+        assert!(row_is_inside(6, 9..=11));
+        assert!(column_is_inside(10, 1..=7));
+    }
+
+    #[test]
+    fn line_leaving_the_shape_is_outside() {
+        assert!(!row_is_inside(2, 2..=11));
+        assert!(!row_is_inside(1, 6..=11));
+        assert!(!row_is_inside(6, 8..=11));
+        assert!(!row_is_inside(7, 0..=11));
+        assert!(!column_is_inside(2, 1..=5));
+        assert!(!column_is_inside(8, 1..=7));
+    }
+
+    #[test]
+    fn single_point_on_vertex() {
+        assert!(row_is_inside(5, 2..=2));
+        assert!(row_is_inside(1, 11..=11));
+        assert!(!row_is_inside(1, 0..=0));
+    }
+
+    #[test]
+    fn adjacent_edges() {
+        // Found by the differential tests with adjacent edges allowed, e.g.
+        // the edges at x = 11 and x = 12 leave no gap between them.
+        let coords = [
+            (3, 9), (3, 10), (8, 10), (8, 13), (11, 13), (11, 17), (12, 17), (12, 14), (16, 14), (16, 13), (18, 13),
+            (18, 2), (16, 2), (16, 9), (12, 9), (12, 10), (11, 10), (11, 9), (8, 9), (8, 2), (4, 2), (4, 9)
+        ];
+        let polygon = RectilinearPolygon::new(coords.into_iter().map(Point2::from).collect()).unwrap();
+        let (horizonal_edges, vertical_edges) = polygon_edges(&polygon);
+        assert!(line_is_inside((10, 3..19), &horizonal_edges, &vertical_edges));
+        assert!(line_is_inside((13, 8..19), &horizonal_edges, &vertical_edges));
+        assert!(!line_is_inside((15, 11..17), &horizonal_edges, &vertical_edges));
+        assert_eq!(max_inclosed_area(&polygon), 55);
+    }
+
+    #[test]
+    fn parse_rejects_diagonal_edges() {
+        let error = Day9.parse("0,0\n0,2\n2,2\n1,0\n").err().unwrap();
+        assert_eq!((error.line, error.found.as_str()), (4, "'1,0'"));
+        assert!(Day9.parse("0,0\n0,2\n2,2\n").is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use crate::generator::Rng;
+use std::panic::{self, AssertUnwindSafe};
+
+// Differential testing: generate many cases, compare a solver against a slow
+// reference and shrink the first disagreement to something small enough to
+// debug by hand.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<T> {
+    // Seed of the Rng the failing case was generated from.
+    pub seed: u64,
+    pub original: T,
+    pub shrunk: T,
+    pub shrink_steps: usize
+}
+
+const MAX_SHRINK_STEPS: usize = 10_000;
+
+// A case fails if `fails` returns true or panics.
+fn fails_or_panics<T>(fails: &impl Fn(&T) -> bool, case: &T) -> bool {
+    panic::catch_unwind(AssertUnwindSafe(|| fails(case))).unwrap_or(true)
+}
+
+// Greedily replaces the case with the first smaller candidate that still fails
+// until none do. Returns the smallest case found and the number of steps.
+pub fn shrink<T>(case: T, candidates: impl Fn(&T) -> Vec<T>, fails: impl Fn(&T) -> bool) -> (T, usize) {
+    let mut case = case;
+    let mut steps = 0;
+    while steps < MAX_SHRINK_STEPS {
+        let Some(smaller) = candidates(&case).into_iter().find(|candidate| fails_or_panics(&fails, candidate)) else { break; };
+        case = smaller;
+        steps += 1;
+    }
+    (case, steps)
+}
+
+// Runs `fails` on cases generated from seeds 0..num_cases and shrinks the first
+// one that fails.
+pub fn check<T: Clone>(
+    num_cases: u64,
+    generate: impl Fn(&mut Rng) -> T,
+    candidates: impl Fn(&T) -> Vec<T>,
+    fails: impl Fn(&T) -> bool
+) -> Result<(), Failure<T>> {
+    for seed in 0..num_cases {
+        let case = generate(&mut Rng::new(seed));
+        if fails_or_panics(&fails, &case) {
+            let (shrunk, shrink_steps) = shrink(case.clone(), candidates, fails);
+            return Err(Failure { seed, original: case, shrunk, shrink_steps });
+        }
+    }
+    Ok(())
+}
+
+// Shrinking candidates for puzzle text: each line removed in turn.
+pub fn without_each_line(text: &str) -> Vec<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    (0..lines.len()).map(|i| {
+        lines.iter().enumerate().filter(|&(j, _)| i != j).map(|(_, line)| format!("{}\n", line)).collect()
+    }).collect()
+}
+
+// Shrinking candidates for puzzle text: each number halved and decremented in
+// turn, never going below `min`.
+pub fn with_smaller_numbers(text: &str, min: u64) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let mut start = 0;
+    while let Some(offset) = text[start..].find(|c: char| c.is_ascii_digit()) {
+        let begin = start + offset;
+        let end = text[begin..].find(|c: char| !c.is_ascii_digit()).map_or(text.len(), |len| begin + len);
+        if let Ok(n) = text[begin..end].parse::<u64>() {
+            let mut smaller = vec![n / 2, n.saturating_sub(1)];
+            smaller.dedup();
+            for m in smaller.into_iter().filter(|&m| m >= min && m < n) {
+                candidates.push(format!("{}{}{}", &text[..begin], m, &text[end..]));
+            }
+        }
+        start = end;
+    }
+    candidates
+}
+
+// Shrinking candidates for puzzle text: each occurrence of `from` replaced by
+// `to` in turn.
+pub fn with_char_replaced(text: &str, from: char, to: char) -> Vec<String> {
+    text.match_indices(from).map(|(i, _)| {
+        format!("{}{}{}", &text[..i], to, &text[i + from.len_utf8()..])
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinks_to_a_minimal_failure() {
+        // Any list containing a number over 100 fails.
+        let failure = check(
+            100,
+            |rng| (0..10).map(|_| rng.below(200)).collect::<Vec<u64>>(),
+            |case| (0..case.len()).flat_map(|i| {
+                let mut without = case.clone();
+                without.remove(i);
+                let mut halved = case.clone();
+                halved[i] /= 2;
+                [without, halved]
+            }).collect(),
+            |case| case.iter().any(|&n| n > 100)
+        ).unwrap_err();
+        assert_eq!(failure.seed, 0);
+        assert_eq!(failure.shrunk.len(), 1);
+        assert!((101..=200).contains(&failure.shrunk[0]));
+    }
+
+    #[test]
+    fn panics_count_as_failures() {
+        let failure = check(10, |rng| rng.below(10), |&n| (0..n).collect(), |&n| {
+            assert!(n < 5);
+            false
+        }).unwrap_err();
+        assert_eq!(failure.shrunk, 5);
+    }
+
+    #[test]
+    fn passing_checks() {
+        assert_eq!(check(10, |rng| rng.below(10), |_| vec![], |_| false), Ok(()));
+    }
+
+    #[test]
+    fn text_candidates() {
+        assert_eq!(without_each_line("a\nb\n"), ["b\n", "a\n"]);
+        assert_eq!(with_smaller_numbers("L10,3", 1), ["L5,3", "L9,3", "L10,1", "L10,2"]);
+        assert_eq!(with_smaller_numbers("1 0", 1), Vec::<String>::new());
+        assert_eq!(with_char_replaced("^.^", '^', '.'), ["..^", "^.."]);
+    }
+}
@@ -0,0 +1,429 @@
+use crate::util::{Point2, Point3};
+use std::collections::HashSet;
+
+// SplitMix64: small, fast and good enough to drive input generation without
+// pulling in a dependency. The same seed always produces the same input.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in 0..n, n must be non-zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    // Uniform in low..=high.
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenOptions {
+    pub seed: u64,
+    // Roughly the number of records in the input: lines, ranges, machines,
+    // regions, etc. Each generator documents how it is interpreted.
+    pub size: usize
+}
+
+pub fn generate(day: u32, options: &GenOptions) -> Option<String> {
+    let mut rng = Rng::new(options.seed);
+    let size = options.size.max(1);
+    let input = match day {
+        1 => dial_rotations(&mut rng, size),
+        2 => id_ranges(&mut rng, size),
+        3 => battery_banks(&mut rng, size),
+        4 => roll_grid(&mut rng, size),
+        5 => ingredient_database(&mut rng, size),
+        6 => worksheet(&mut rng, size),
+        7 => splitter_manifold(&mut rng, size),
+        8 => junction_boxes(&mut rng, size),
+        9 => rectilinear_polygon(&mut rng, size),
+        10 => machines(&mut rng, size),
+        11 => device_graph(&mut rng, size),
+        12 => shape_packing(&mut rng, size),
+        _ => return None
+    };
+    Some(input)
+}
+
+fn lines(lines: impl IntoIterator<Item=String>) -> String {
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+// `size` rotations of up to 999 clicks.
+pub fn dial_rotations(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+        format!("{}{}", direction, rng.range(0, 999))
+    }))
+}
+
+// `size` comma separated ranges of at most 10,000 IDs with up to ten digits.
+pub fn id_ranges(rng: &mut Rng, size: usize) -> String {
+    let ranges: Vec<String> = (0..size).map(|_| {
+        let digits = rng.range(1, 10) as u32;
+        let low = rng.range(10u64.pow(digits - 1), 10u64.pow(digits) - 1);
+        let high = low + rng.range(0, 10_000);
+        format!("{}-{}", low, high)
+    }).collect();
+    ranges.join(",") + "\n"
+}
+
+// `size` banks of 12 to 100 batteries with joltages 1-9.
+pub fn battery_banks(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let len = rng.range(12, 100);
+        (0..len).map(|_| char::from(b'0' + rng.range(1, 9) as u8)).collect()
+    }))
+}
+
+// A `size` by `size` grid with roughly 60% of the cells holding rolls.
+pub fn roll_grid(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| (0..size).map(|_| if rng.chance(0.6) { '@' } else { '.' }).collect()))
+}
+
+// `size` fresh ID ranges, which sometimes overlap, and `size` IDs to look up,
+// all below 100 * `size`.
+pub fn ingredient_database(rng: &mut Rng, size: usize) -> String {
+    let max_id = 100 * size as u64;
+    let mut out: Vec<String> = (0..size).map(|_| {
+        let low = rng.range(1, max_id);
+        let high = (low + rng.range(0, max_id / 100)).min(max_id);
+        format!("{}-{}", low, high)
+    }).collect();
+    out.push(String::new());
+    out.extend((0..size).map(|_| rng.range(1, max_id).to_string()));
+    lines(out)
+}
+
+// `size` problems of three or four numbers with one to four digits. Within a
+// problem the numbers are all left or all right aligned, and problems are
+// separated by a single column of spaces.
+pub fn worksheet(rng: &mut Rng, size: usize) -> String {
+    let num_rows = rng.range(3, 4) as usize;
+    let mut rows = vec![String::new(); num_rows];
+    let mut operators = String::new();
+    for problem in 0..size {
+        if problem > 0 {
+            rows.iter_mut().for_each(|row| row.push(' '));
+            operators.push(' ');
+        }
+        let numbers: Vec<String> = (0..num_rows).map(|_| {
+            let digits = rng.range(1, 4);
+            (0..digits).map(|_| char::from(b'0' + rng.range(0, 9) as u8)).collect()
+        }).collect();
+        let width = numbers.iter().map(String::len).max().unwrap();
+        let left = rng.chance(0.5);
+        for (row, number) in rows.iter_mut().zip(&numbers) {
+            if left {
+                row.push_str(&format!("{:<width$}", number));
+            } else {
+                row.push_str(&format!("{:>width$}", number));
+            }
+        }
+        let operator = if rng.chance(0.5) { '*' } else { '+' };
+        operators.push_str(&format!("{:<width$}", operator));
+    }
+    lines(rows.into_iter().chain(std::iter::once(operators)))
+}
+
+// A manifold `2 * size + 1` columns wide and `2 * size` rows deep, with the
+// start in the middle of the top row and splitters on every other row, never
+// in the outermost columns.
+pub fn splitter_manifold(rng: &mut Rng, size: usize) -> String {
+    let width = 2 * size + 1;
+    let mut rows = Vec::new();
+    rows.push((0..width).map(|i| if i == size { 'S' } else { '.' }).collect::<String>());
+    for row in 1..2 * size {
+        if row.is_multiple_of(2) {
+            rows.push((0..width).map(|i| if i > 0 && i + 1 < width && rng.chance(0.3) { '^' } else { '.' }).collect());
+        } else {
+            rows.push(".".repeat(width));
+        }
+    }
+    lines(rows)
+}
+
+// `size` (at least three) distinct junction boxes with coordinates below 100,000.
+pub fn junction_boxes(rng: &mut Rng, size: usize) -> String {
+    let mut seen = HashSet::new();
+    let mut boxes = Vec::new();
+    while boxes.len() < size.max(3) {
+        let coord = Point3::new(rng.below(100_000), rng.below(100_000), rng.below(100_000));
+        if seen.insert(coord) {
+            boxes.push(coord.to_string());
+        }
+    }
+    lines(boxes)
+}
+
+// Maps lattice positions 0..=n onto strictly increasing coordinates between 1
+// and `max_gap` apart, so distinct edges may end up on adjacent tiles.
+fn spread_coordinates(rng: &mut Rng, n: u64, max_gap: u64) -> Vec<u64> {
+    let mut coordinate = rng.range(0, max_gap);
+    (0..=n).map(|_| {
+        let current = coordinate;
+        coordinate += rng.range(1, max_gap);
+        current
+    }).collect()
+}
+
+// The vertices of a polygon made of `size` adjacent columns of unit cells.
+// Neighbouring columns always overlap, so the polygon is connected and has no
+// holes. Consecutive vertices share an axis.
+pub fn polygon_vertices(rng: &mut Rng, size: usize, max_gap: u64) -> Vec<Point2<u64>> {
+    let height = (size as u64).max(2);
+    let mut columns: Vec<(u64, u64)> = Vec::new();
+    for _ in 0..size {
+        let column = loop {
+            let bottom = rng.range(0, height - 1);
+            let top = rng.range(bottom, height - 1);
+            let overlaps = columns.last().is_none_or(|&(b, t)| bottom.max(b) <= top.min(t));
+            if overlaps {
+                break (bottom, top);
+            }
+        };
+        columns.push(column);
+    }
+    // Trace the outline clockwise (with y increasing upwards) as lattice
+    // points, starting from the bottom left corner.
+    let k = columns.len() as u64;
+    let mut points = vec![[0, columns[0].0], [0, columns[0].1 + 1]];
+    for (i, &(_, top)) in columns.iter().enumerate() {
+        points.push([i as u64 + 1, top + 1]);
+        if let Some(&(_, next_top)) = columns.get(i + 1) {
+            points.push([i as u64 + 1, next_top + 1]);
+        }
+    }
+    points.push([k, columns[columns.len() - 1].0]);
+    for (i, &(bottom, _)) in columns.iter().enumerate().rev() {
+        points.push([i as u64, bottom]);
+        if i > 0 {
+            points.push([i as u64, columns[i - 1].0]);
+        }
+    }
+    // Drop duplicates and points in the middle of a straight run.
+    points.dedup();
+    if points.first() == points.last() {
+        points.pop();
+    }
+    let n = points.len();
+    let corners: Vec<[u64; 2]> = (0..n).filter(|&i| {
+        let prev = points[(i + n - 1) % n];
+        let next = points[(i + 1) % n];
+        let point = points[i];
+        let straight = |axis: usize| prev[axis] == point[axis] && point[axis] == next[axis];
+        !straight(0) && !straight(1)
+    }).map(|i| points[i]).collect();
+    let xs = spread_coordinates(rng, k, max_gap);
+    let ys = spread_coordinates(rng, height, max_gap);
+    corners.into_iter().map(|[x, y]| Point2::new(xs[x as usize], ys[y as usize])).collect()
+}
+
+pub fn rectilinear_polygon(rng: &mut Rng, size: usize) -> String {
+    lines(polygon_vertices(rng, size, 1000).into_iter().map(|vertex| vertex.to_string()))
+}
+
+// `size` machines with 3 to 8 lights and up to two more buttons than lights.
+// The light and joltage goals come from random presses so both are reachable.
+pub fn machines(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let num_lights = rng.range(3, 8) as usize;
+        let num_buttons = rng.range(2, num_lights as u64 + 2) as usize;
+        let mut buttons: Vec<Vec<usize>> = (0..num_buttons).map(|_| {
+            (0..num_lights).filter(|_| rng.chance(0.4)).collect()
+        }).collect();
+        for light in 0..num_lights {
+            if !buttons.iter().any(|button| button.contains(&light)) {
+                let i = rng.index(num_buttons);
+                buttons[i].push(light);
+            }
+        }
+        for button in buttons.iter_mut() {
+            if button.is_empty() {
+                button.push(rng.index(num_lights));
+            }
+            button.sort();
+            button.dedup();
+        }
+        let mut lights = vec![false; num_lights];
+        for button in &buttons {
+            if rng.chance(0.5) {
+                button.iter().for_each(|&light| lights[light] = !lights[light]);
+            }
+        }
+        let mut joltages = vec![0; num_lights];
+        for button in &buttons {
+            let presses = rng.range(0, 10);
+            button.iter().for_each(|&light| joltages[light] += presses);
+        }
+        let lights: String = lights.iter().map(|&on| if on { '#' } else { '.' }).collect();
+        let buttons: Vec<String> = buttons.iter().map(|button| {
+            let indices: Vec<String> = button.iter().map(usize::to_string).collect();
+            format!("({})", indices.join(","))
+        }).collect();
+        let joltages: Vec<String> = joltages.iter().map(u64::to_string).collect();
+        format!("[{}] {} {{{}}}", lights, buttons.join(" "), joltages.join(","))
+    }))
+}
+
+// An acyclic graph of `size` devices plus you, svr, dac, fft and out, where
+// every device has one to three outputs to later devices, so every path ends
+// at out. There is always a route from svr through dac and fft. Lines are
+// shuffled.
+pub fn device_graph(rng: &mut Rng, size: usize) -> String {
+    let mut names: Vec<String> = Vec::new();
+    let mut seen: HashSet<String> = ["you", "svr", "dac", "fft", "out"].iter().map(|s| s.to_string()).collect();
+    while names.len() < size {
+        let name: String = (0..3).map(|_| char::from(b'a' + rng.below(26) as u8)).collect();
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    // Topological order: you and svr at the start, out at the end and dac and
+    // fft somewhere in between.
+    let mut order = names;
+    order.insert(rng.index(order.len() + 1), "dac".to_string());
+    order.insert(rng.index(order.len() + 1), "fft".to_string());
+    order.insert(0, "svr".to_string());
+    order.insert(rng.index(3).min(1), "you".to_string());
+    order.push("out".to_string());
+    let n = order.len();
+    let position = |name: &str| order.iter().position(|other| other == name).unwrap();
+    let mut route = [position("svr"), position("dac"), position("fft"), n - 1];
+    route.sort();
+    let mut result: Vec<String> = (0..n - 1).map(|i| {
+        let num_outputs = if rng.chance(0.6) { 1 } else if rng.chance(0.75) { 2 } else { 3 };
+        let mut outputs: Vec<usize> = (0..num_outputs).map(|_| {
+            (i + 1 + rng.index(20)).min(n - 1)
+        }).collect();
+        if let Some(j) = route.iter().position(|&r| r == i) {
+            outputs.push(route[j + 1]);
+        }
+        outputs.sort();
+        outputs.dedup();
+        let outputs: Vec<&str> = outputs.iter().map(|&j| order[j].as_str()).collect();
+        format!("{}: {}", order[i], outputs.join(" "))
+    }).collect();
+    rng.shuffle(&mut result);
+    lines(result)
+}
+
+// Six random 3x3 presents followed by `size` regions between 4x4 and 50x50.
+// Like the real input, each region either has more present area than space or
+// few enough presents that each can have its own 3x3 block.
+pub fn shape_packing(rng: &mut Rng, size: usize) -> String {
+    let mut out = Vec::new();
+    let mut areas = Vec::new();
+    for i in 0..6 {
+        let cells = loop {
+            let cells: Vec<bool> = (0..9).map(|_| rng.chance(0.7)).collect();
+            // Filling the middle row and column keeps the shape 3x3 and connected.
+            if cells[1] && cells[3] && cells[4] && cells[5] && cells[7] {
+                break cells;
+            }
+        };
+        areas.push(cells.iter().filter(|&&c| c).count());
+        out.push(format!("{}:", i));
+        for row in cells.chunks(3) {
+            out.push(row.iter().map(|&c| if c { '#' } else { '.' }).collect());
+        }
+        out.push(String::new());
+    }
+    for _ in 0..size {
+        let width = rng.range(4, 50) as usize;
+        let height = rng.range(4, 50) as usize;
+        let blocks = (width / 3) * (height / 3);
+        let mut counts = [0; 6];
+        if rng.chance(0.5) {
+            for _ in 0..rng.range(0, blocks as u64) {
+                counts[rng.index(6)] += 1;
+            }
+        } else {
+            while counts.iter().zip(&areas).map(|(count, area)| count * area).sum::<usize>() <= width * height {
+                counts[rng.index(6)] += 1 + rng.index(4);
+            }
+        }
+        let counts: Vec<String> = counts.iter().map(usize::to_string).collect();
+        out.push(format!("{}x{}: {}", width, height, counts.join(" ")));
+    }
+    lines(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use crate::solution::Part;
+    use crate::util::RectilinearPolygon;
+
+    #[test]
+    fn generated_inputs_parse() {
+        for solution in days::ALL {
+            for seed in 0..20 {
+                let input = generate(solution.day(), &GenOptions { seed, size: 1 + seed as usize * 3 }).unwrap();
+                if let Err(err) = solution.parse(&input) {
+                    panic!("day {} seed {}: {}\n{}", solution.day(), seed, err, input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        for solution in days::ALL {
+            for seed in 0..5 {
+                let input = generate(solution.day(), &GenOptions { seed, size: 8 }).unwrap();
+                for part in Part::ALL {
+                    solution.run(&input, part).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generation_is_deterministic() {
+        let options = GenOptions { seed: 42, size: 10 };
+        assert_eq!(generate(9, &options), generate(9, &options));
+        assert_ne!(generate(9, &options), generate(9, &GenOptions { seed: 43, ..options }));
+    }
+
+    #[test]
+    fn polygon_edges_are_axis_aligned() {
+        let mut rng = Rng::new(7);
+        for size in 1..30 {
+            let vertices = polygon_vertices(&mut rng, size, 2 + size as u64);
+            assert!(vertices.len().is_multiple_of(2));
+            assert!(RectilinearPolygon::new(vertices).is_ok());
+        }
+    }
+}
@@ -0,0 +1,316 @@
+// Linear algebra over GF(2), the integers modulo 2, where adding is XOR.
+// Vectors are packed 64 bits to a word so row operations work a word at a
+// time.
+
+use std::ops::BitXorAssign;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize
+}
+
+impl BitVec {
+    // `len` zero bits.
+    pub fn new(len: usize) -> Self {
+        BitVec { words: vec![0; len.div_ceil(64)], len }
+    }
+
+    // `len` bits with those at `indices` set.
+    pub fn from_indices(len: usize, indices: impl IntoIterator<Item=usize>) -> Self {
+        let mut bits = Self::new(len);
+        indices.into_iter().for_each(|i| bits.set(i, true));
+        bits
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "bit {} out of range for {} bits", i, self.len);
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < self.len, "bit {} out of range for {} bits", i, self.len);
+        let mask = 1 << (i % 64);
+        if value { self.words[i / 64] |= mask } else { self.words[i / 64] &= !mask }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    // The indices of the set bits in increasing order.
+    pub fn iter_ones(&self) -> impl Iterator<Item=usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    i * 64 + bit
+                })
+            })
+        })
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item=bool>>(bits: I) -> Self {
+        let mut vec = BitVec::new(0);
+        for bit in bits {
+            if vec.len.is_multiple_of(64) {
+                vec.words.push(0);
+            }
+            vec.len += 1;
+            vec.set(vec.len - 1, bit);
+        }
+        vec
+    }
+}
+
+impl BitXorAssign<&BitVec> for BitVec {
+    fn bitxor_assign(&mut self, rhs: &BitVec) {
+        assert_eq!(self.len, rhs.len, "XOR of bit vectors with different lengths");
+        self.words.iter_mut().zip(&rhs.words).for_each(|(a, b)| *a ^= b);
+    }
+}
+
+// Equations a1 x1 + ... + an xn = b over GF(2).
+#[derive(Debug, Clone)]
+pub struct LinearSystem {
+    num_vars: usize,
+    // Each row holds the coefficients followed by the right hand side.
+    rows: Vec<BitVec>
+}
+
+// Every solution of a system: the particular solution plus any combination of
+// the nullspace basis vectors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolutionSpace {
+    pub particular: BitVec,
+    pub nullspace: Vec<BitVec>
+}
+
+impl LinearSystem {
+    pub fn new(num_vars: usize) -> Self {
+        LinearSystem { num_vars, rows: Vec::new() }
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    pub fn add_equation(&mut self, coefficients: &BitVec, rhs: bool) {
+        assert_eq!(coefficients.len(), self.num_vars);
+        let mut row = BitVec::from_indices(self.num_vars + 1, coefficients.iter_ones());
+        row.set(self.num_vars, rhs);
+        self.rows.push(row);
+    }
+
+    // Reduces the rows to reduced row echelon form by Gauss-Jordan
+    // elimination, returning the pivot column of each of the leading rows.
+    fn eliminate(&self) -> (Vec<BitVec>, Vec<usize>) {
+        let mut rows = self.rows.clone();
+        let mut pivots = Vec::new();
+        for col in 0..self.num_vars {
+            let r = pivots.len();
+            let Some(pivot) = (r..rows.len()).find(|&i| rows[i].get(col)) else { continue; };
+            rows.swap(r, pivot);
+            let pivot_row = rows[r].clone();
+            for (i, row) in rows.iter_mut().enumerate() {
+                if i != r && row.get(col) {
+                    *row ^= &pivot_row;
+                }
+            }
+            pivots.push(col);
+        }
+        (rows, pivots)
+    }
+
+    // The number of independent equations.
+    pub fn rank(&self) -> usize {
+        self.eliminate().1.len()
+    }
+
+    // All solutions, or None if the equations are inconsistent.
+    pub fn solve(&self) -> Option<SolutionSpace> {
+        let (rows, pivots) = self.eliminate();
+        // Rows below the pivots have no coefficients left, so a set right hand
+        // side there reads 0 = 1.
+        if rows[pivots.len()..].iter().any(|row| row.get(self.num_vars)) {
+            return None;
+        }
+        let mut particular = BitVec::new(self.num_vars);
+        for (row, &col) in rows.iter().zip(&pivots) {
+            particular.set(col, row.get(self.num_vars));
+        }
+        let mut is_pivot = vec![false; self.num_vars];
+        pivots.iter().for_each(|&col| is_pivot[col] = true);
+        let nullspace = (0..self.num_vars).filter(|&free| !is_pivot[free]).map(|free| {
+            let mut vector = BitVec::from_indices(self.num_vars, [free]);
+            for (row, &col) in rows.iter().zip(&pivots) {
+                vector.set(col, row.get(free));
+            }
+            vector
+        }).collect();
+        Some(SolutionSpace { particular, nullspace })
+    }
+}
+
+impl SolutionSpace {
+    // The number of free variables; there are 2^dimension solutions.
+    pub fn dimension(&self) -> usize {
+        self.nullspace.len()
+    }
+
+    // A solution with the fewest set bits, found by branch and bound over
+    // whether to add each nullspace vector in turn. Once the last vector
+    // touching a bit has been decided that bit can't change, so the set bits
+    // already settled bound the weight of every solution below a branch. The
+    // search is still exponential in the worst case.
+    pub fn min_weight(&self) -> BitVec {
+        // settled[k] lists the bits that no vector from k onwards touches,
+        // but one before k does (or none at all for k = 0).
+        let mut settled = vec![Vec::new(); self.dimension() + 1];
+        for bit in 0..self.particular.len() {
+            let last = self.nullspace.iter().rposition(|vector| vector.get(bit));
+            settled[last.map_or(0, |i| i + 1)].push(bit);
+        }
+        let mut current = self.particular.clone();
+        let mut best = (current.count_ones(), current.clone());
+        let weight = settled[0].iter().filter(|&&bit| current.get(bit)).count();
+        self.branch(0, weight, &settled, &mut current, &mut best);
+        best.1
+    }
+
+    // Decides the nullspace vectors from `depth` on, given the number of set
+    // bits among those already settled.
+    fn branch(&self, depth: usize, weight: usize, settled: &[Vec<usize>], current: &mut BitVec, best: &mut (usize, BitVec)) {
+        if weight >= best.0 {
+            return;
+        }
+        if depth == self.dimension() {
+            *best = (weight, current.clone());
+            return;
+        }
+        for add in [false, true] {
+            if add {
+                *current ^= &self.nullspace[depth];
+            }
+            let weight = weight + settled[depth + 1].iter().filter(|&&bit| current.get(bit)).count();
+            self.branch(depth + 1, weight, settled, current, best);
+            if add {
+                *current ^= &self.nullspace[depth];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(text: &str) -> BitVec {
+        text.chars().map(|c| c == '1').collect()
+    }
+
+    fn system(equations: &[(&str, bool)]) -> LinearSystem {
+        let mut system = LinearSystem::new(equations[0].0.len());
+        equations.iter().for_each(|&(coefficients, rhs)| system.add_equation(&bits(coefficients), rhs));
+        system
+    }
+
+    fn satisfies(system: &LinearSystem, x: &BitVec) -> bool {
+        system.rows.iter().all(|row| {
+            let lhs = x.iter_ones().filter(|&i| row.get(i)).count() % 2 == 1;
+            lhs == row.get(system.num_vars)
+        })
+    }
+
+    #[test]
+    fn bit_vectors() {
+        let mut v = BitVec::from_indices(130, [0, 63, 64, 129]);
+        assert_eq!(v.iter_ones().collect::<Vec<_>>(), [0, 63, 64, 129]);
+        assert_eq!(v.count_ones(), 4);
+        v ^= &BitVec::from_indices(130, [63, 100]);
+        assert_eq!(v.iter_ones().collect::<Vec<_>>(), [0, 64, 100, 129]);
+        v.set(0, false);
+        assert!(!v.get(0) && v.get(64));
+        assert_eq!(bits("0110").iter_ones().collect::<Vec<_>>(), [1, 2]);
+        assert!(BitVec::new(70).is_zero());
+    }
+
+    #[test]
+    fn unique_solution() {
+        // x0 + x1 = 1, x1 + x2 = 0, x0 + x1 + x2 = 0.
+        let system = system(&[("110", true), ("011", false), ("111", false)]);
+        let space = system.solve().unwrap();
+        assert_eq!(space.particular, bits("011"));
+        assert_eq!(space.dimension(), 0);
+        assert_eq!(system.rank(), 3);
+    }
+
+    #[test]
+    fn inconsistent() {
+        assert_eq!(system(&[("110", true), ("011", true), ("101", true)]).solve(), None);
+    }
+
+    #[test]
+    fn nullspace_spans_every_solution() {
+        let system = system(&[("11010", true), ("01101", false), ("11010", true)]);
+        let space = system.solve().unwrap();
+        assert_eq!(space.dimension(), 3);
+        assert!(satisfies(&system, &space.particular));
+        let solutions: Vec<BitVec> = (0..1u32 << 5).map(|n| (0..5).map(|i| n >> i & 1 == 1).collect()).collect();
+        let brute_force: Vec<&BitVec> = solutions.iter().filter(|x| satisfies(&system, x)).collect();
+        assert_eq!(brute_force.len(), 1 << space.dimension());
+        for vector in &space.nullspace {
+            let mut x = space.particular.clone();
+            x ^= vector;
+            assert!(satisfies(&system, &x));
+        }
+        let min = brute_force.iter().map(|x| x.count_ones()).min().unwrap();
+        assert_eq!(space.min_weight().count_ones(), min);
+        assert!(satisfies(&system, &space.min_weight()));
+    }
+
+    #[test]
+    fn min_weight_agrees_with_brute_force() {
+        let mut rng = crate::generator::Rng::new(3);
+        for _ in 0..200 {
+            let num_vars = 1 + rng.index(8);
+            let mut system = LinearSystem::new(num_vars);
+            for _ in 0..rng.index(5) {
+                let coefficients: BitVec = (0..num_vars).map(|_| rng.chance(0.5)).collect();
+                system.add_equation(&coefficients, rng.chance(0.5));
+            }
+            let Some(space) = system.solve() else { continue; };
+            let min = (0..1u32 << num_vars).map(|n| (0..num_vars).map(|i| n >> i & 1 == 1).collect::<BitVec>())
+                .filter(|x| satisfies(&system, x)).map(|x| x.count_ones()).min().unwrap();
+            let best = space.min_weight();
+            assert_eq!(best.count_ones(), min);
+            assert!(satisfies(&system, &best));
+        }
+    }
+
+    #[test]
+    fn min_weight_with_many_free_variables() {
+        // x0 + ... + x99 = 1 has 2^99 solutions, but a single set bit is best.
+        let mut system = LinearSystem::new(100);
+        system.add_equation(&BitVec::from_indices(100, 0..100), true);
+        let space = system.solve().unwrap();
+        assert_eq!(space.dimension(), 99);
+        assert_eq!(space.min_weight().count_ones(), 1);
+    }
+}
@@ -0,0 +1,365 @@
+use crate::parse::{self, ParseError};
+use crate::util::{CycleError, Memo};
+use std::collections::{HashMap, VecDeque};
+
+// A directed graph whose nodes are named. Names are interned to indices in the
+// order they are first seen, and algorithms work on the indices.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    indices: HashMap<String, usize>,
+    children: Vec<Vec<usize>>
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // Parses lines such as "aaa: bbb ccc", each giving a node's children.
+    pub fn from_adjacency(input: &str) -> Result<Self, ParseError> {
+        let mut graph = Graph::new();
+        for line in parse::lines(input) {
+            let (src, dsts) = line.key_values()?;
+            let src = graph.add_node(src);
+            for dst in dsts {
+                let dst = graph.add_node(dst);
+                graph.add_edge(src, dst);
+            }
+        }
+        Ok(graph)
+    }
+
+    pub fn from_edges<'a>(edges: impl IntoIterator<Item=(&'a str, &'a str)>) -> Self {
+        let mut graph = Graph::new();
+        for (src, dst) in edges {
+            let src = graph.add_node(src);
+            let dst = graph.add_node(dst);
+            graph.add_edge(src, dst);
+        }
+        graph
+    }
+
+    // The index of the node called `name`, adding it if it is new.
+    pub fn add_node(&mut self, name: &str) -> usize {
+        if let Some(&index) = self.indices.get(name) {
+            return index;
+        }
+        let index = self.names.len();
+        self.names.push(name.to_string());
+        self.indices.insert(name.to_string(), index);
+        self.children.push(Vec::new());
+        index
+    }
+
+    pub fn add_edge(&mut self, src: usize, dst: usize) {
+        self.children[src].push(dst);
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.indices.get(name).copied()
+    }
+
+    pub fn name(&self, node: usize) -> &str {
+        &self.names[node]
+    }
+
+    pub fn children(&self, node: usize) -> &[usize] {
+        &self.children[node]
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item=(usize, usize)> + '_ {
+        self.children.iter().enumerate().flat_map(|(src, dsts)| dsts.iter().map(move |&dst| (src, dst)))
+    }
+
+    // The same graph with every edge pointing the other way. Node indices are
+    // unchanged.
+    pub fn reversed(&self) -> Self {
+        let mut children = vec![Vec::new(); self.len()];
+        for (src, dst) in self.edges() {
+            children[dst].push(src);
+        }
+        Graph { names: self.names.clone(), indices: self.indices.clone(), children }
+    }
+
+    // Every node ordered so that edges point forwards, or None if the graph has
+    // a cycle.
+    pub fn topological_sort(&self) -> Option<Vec<usize>> {
+        let mut in_degree = vec![0; self.len()];
+        for (_, dst) in self.edges() {
+            in_degree[dst] += 1;
+        }
+        let mut ready: VecDeque<usize> = (0..self.len()).filter(|&node| in_degree[node] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = ready.pop_front() {
+            order.push(node);
+            for &child in self.children(node) {
+                in_degree[child] -= 1;
+                if in_degree[child] == 0 {
+                    ready.push_back(child);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+
+    // The nodes reachable from `start` in breadth-first order, starting with
+    // `start` itself.
+    pub fn bfs(&self, start: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.len()];
+        seen[start] = true;
+        let mut order = vec![start];
+        let mut i = 0;
+        while let Some(&node) = order.get(i) {
+            for &child in self.children(node) {
+                if !seen[child] {
+                    seen[child] = true;
+                    order.push(child);
+                }
+            }
+            i += 1;
+        }
+        order
+    }
+
+    // The nodes reachable from `start` in depth-first preorder, visiting
+    // children in the order their edges were added.
+    pub fn dfs(&self, start: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            if seen[node] {
+                continue;
+            }
+            seen[node] = true;
+            order.push(node);
+            stack.extend(self.children(node).iter().rev().filter(|&&child| !seen[child]));
+        }
+        order
+    }
+
+    pub fn reachable(&self, start: usize) -> Vec<bool> {
+        let mut reachable = vec![false; self.len()];
+        self.bfs(start).into_iter().for_each(|node| reachable[node] = true);
+        reachable
+    }
+
+    // The number of edges on the shortest path from `start` to every node, or
+    // None for nodes that can't be reached. Every edge counts as length 1.
+    pub fn unweighted_distances(&self, start: usize) -> Vec<Option<usize>> {
+        self.unweighted_shortest_path_tree(start).into_iter().map(|entry| entry.map(|(distance, _)| distance)).collect()
+    }
+
+    // One of the paths from `start` to `end` with the fewest edges, including
+    // both ends.
+    pub fn unweighted_shortest_path(&self, start: usize, end: usize) -> Option<Vec<usize>> {
+        let tree = self.unweighted_shortest_path_tree(start);
+        tree[end]?;
+        let mut path = vec![end];
+        while let Some((_, Some(parent))) = tree[*path.last().unwrap()] {
+            path.push(parent);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // For each node reached, its distance in edges from `start` and its parent
+    // on a shortest path, found by breadth-first search.
+    fn unweighted_shortest_path_tree(&self, start: usize) -> Vec<Option<(usize, Option<usize>)>> {
+        let mut tree = vec![None; self.len()];
+        tree[start] = Some((0, None));
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            let (distance, _) = tree[node].unwrap();
+            for &child in self.children(node) {
+                if tree[child].is_none() {
+                    tree[child] = Some((distance + 1, Some(node)));
+                    queue.push_back(child);
+                }
+            }
+        }
+        tree
+    }
+
+    // Every node in the order its depth-first search from `start` finishes,
+    // skipping nodes already marked as seen.
+    fn postorder(&self, start: usize, seen: &mut [bool], order: &mut Vec<usize>) {
+        if seen[start] {
+            return;
+        }
+        seen[start] = true;
+        let mut stack = vec![(start, 0)];
+        while let Some((node, next_child)) = stack.last_mut() {
+            if let Some(&child) = self.children[*node].get(*next_child) {
+                *next_child += 1;
+                if !seen[child] {
+                    seen[child] = true;
+                    stack.push((child, 0));
+                }
+            } else {
+                order.push(*node);
+                stack.pop();
+            }
+        }
+    }
+
+    // The strongly connected components, found with Kosaraju's algorithm. They
+    // are listed in topological order of the condensed graph.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.len()];
+        let mut finished = Vec::with_capacity(self.len());
+        for node in 0..self.len() {
+            self.postorder(node, &mut seen, &mut finished);
+        }
+        let reversed = self.reversed();
+        let mut seen = vec![false; self.len()];
+        let mut components = Vec::new();
+        for &node in finished.iter().rev() {
+            let mut component = Vec::new();
+            reversed.postorder(node, &mut seen, &mut component);
+            if !component.is_empty() {
+                component.sort();
+                components.push(component);
+            }
+        }
+        components
+    }
+
+    // The number of distinct paths from `src` to `dst`, see PathCounter. Use a
+    // PathCounter directly to share work between several queries.
+    pub fn count_paths(&self, src: usize, dst: usize) -> Result<u64, CycleError<usize>> {
+        PathCounter::new(self).count(src, dst)
+    }
+}
+
+// Counts the paths between pairs of nodes of a graph. The reversed graph is
+// built once, and the counts to each destination are kept for later queries
+// to the same destination.
+pub struct PathCounter<'a> {
+    graph: &'a Graph,
+    reversed: Graph,
+    // For each destination queried so far, the nodes that can reach it and
+    // the number of paths to it from the nodes counted so far.
+    destinations: HashMap<usize, (Vec<bool>, Memo<usize, u64>)>
+}
+
+impl<'a> PathCounter<'a> {
+    pub fn new(graph: &'a Graph) -> Self {
+        PathCounter { graph, reversed: graph.reversed(), destinations: HashMap::new() }
+    }
+
+    // The number of distinct paths from `src` to `dst`. A cycle on some path
+    // from `src` to `dst` would allow infinitely many, and is an error naming
+    // one of its nodes. Cycles that can't reach `dst` add no paths, so nodes
+    // that can't reach it are skipped.
+    pub fn count(&mut self, src: usize, dst: usize) -> Result<u64, CycleError<usize>> {
+        let graph = self.graph;
+        let (reaches_dst, memo) = self.destinations.entry(dst).or_insert_with(|| (self.reversed.reachable(dst), Memo::new()));
+        memo.get(src, |memo, &node| {
+            if node == dst {
+                return Ok(1);
+            }
+            graph.children(node).iter().filter(|&&child| reaches_dst[child]).map(|&child| memo.get(child)).sum()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(input: &str) -> Graph {
+        Graph::from_adjacency(input).unwrap()
+    }
+
+    fn names(graph: &Graph, nodes: &[usize]) -> Vec<String> {
+        nodes.iter().map(|&node| graph.name(node).to_string()).collect()
+    }
+
+    #[test]
+    fn names_are_interned() {
+        let g = graph("a: b c\nb: c\nd:\n");
+        assert_eq!(g.len(), 4);
+        assert_eq!(g.index("c"), Some(2));
+        assert_eq!(g.index("e"), None);
+        assert_eq!(names(&g, g.children(0)), ["b", "c"]);
+        assert_eq!(g.edges().count(), 3);
+        assert_eq!(names(&g, g.reversed().children(2)), ["a", "b"]);
+        assert!(Graph::from_adjacency("a b").is_err());
+    }
+
+    #[test]
+    fn from_edges_matches_adjacency() {
+        let g = Graph::from_edges([("a", "b"), ("a", "c"), ("b", "c")]);
+        assert_eq!(g.edges().collect::<Vec<_>>(), graph("a: b c\nb: c\n").edges().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn topological_sort() {
+        let g = graph("c: d\na: b c\nb: d\n");
+        let order = g.topological_sort().unwrap();
+        let position = |name| order.iter().position(|&node| node == g.index(name).unwrap()).unwrap();
+        assert!(g.edges().all(|(src, dst)| position(g.name(src)) < position(g.name(dst))));
+        assert_eq!(graph("a: b\nb: a\n").topological_sort(), None);
+    }
+
+    #[test]
+    fn traversals() {
+        let g = graph("a: b c\nb: d\nc: d e\ne: a\nf: a\n");
+        let a = g.index("a").unwrap();
+        assert_eq!(names(&g, &g.bfs(a)), ["a", "b", "c", "d", "e"]);
+        assert_eq!(names(&g, &g.dfs(a)), ["a", "b", "d", "c", "e"]);
+        assert_eq!(g.reachable(a), [true, true, true, true, true, false]);
+        assert_eq!(g.unweighted_distances(a), [Some(0), Some(1), Some(1), Some(2), Some(2), None]);
+        let f = g.index("f").unwrap();
+        assert_eq!(names(&g, &g.unweighted_shortest_path(f, g.index("e").unwrap()).unwrap()), ["f", "a", "c", "e"]);
+        assert_eq!(g.unweighted_shortest_path(a, f), None);
+    }
+
+    #[test]
+    fn strongly_connected_components() {
+        let g = graph("a: b\nb: c\nc: a d\nd: e\ne: d\nf: e\n");
+        let components: Vec<Vec<String>> = g.strongly_connected_components().iter().map(|c| names(&g, c)).collect();
+        assert_eq!(components, [vec!["f"], vec!["a", "b", "c"], vec!["d", "e"]]);
+    }
+
+    #[test]
+    fn count_paths() {
+        let g = graph("a: b c\nb: d\nc: d e\nd: e\n");
+        let (a, e) = (g.index("a").unwrap(), g.index("e").unwrap());
+        assert_eq!(g.count_paths(a, e), Ok(3));
+        assert_eq!(g.count_paths(e, a), Ok(0));
+        assert_eq!(g.count_paths(a, a), Ok(1));
+        let g = graph("a: b\nb: c d\nc: b\n");
+        let (a, b, d) = (g.index("a").unwrap(), g.index("b").unwrap(), g.index("d").unwrap());
+        assert_eq!(g.count_paths(a, d), Err(CycleError { key: b }));
+        assert_eq!(g.count_paths(d, a), Ok(0));
+        // A cycle that never reaches the destination adds no paths.
+        let g = graph("you: out x\nx: y\ny: x\n");
+        assert_eq!(g.count_paths(g.index("you").unwrap(), g.index("out").unwrap()), Ok(1));
+        assert_eq!(g.count_paths(g.index("x").unwrap(), g.index("out").unwrap()), Ok(0));
+    }
+
+    #[test]
+    fn path_counter_answers_several_queries() {
+        let g = graph("a: b c\nb: d\nc: d e\nd: e\nx: y\ny: x e\n");
+        let node = |name| g.index(name).unwrap();
+        let mut counter = PathCounter::new(&g);
+        assert_eq!(counter.count(node("a"), node("e")), Ok(3));
+        assert_eq!(counter.count(node("b"), node("e")), Ok(1));
+        assert_eq!(counter.count(node("x"), node("e")), Err(CycleError { key: node("x") }));
+        // The failed query leaves the counts it cached intact.
+        assert_eq!(counter.count(node("c"), node("e")), Ok(2));
+        assert_eq!(counter.count(node("a"), node("d")), Ok(2));
+        assert_eq!(counter.count(node("a"), node("e")), Ok(3));
+    }
+}
@@ -0,0 +1,77 @@
+use super::{Overflow, Rational};
+
+// A linear system A x = b in reduced row echelon form: each row has a leading
+// one in its pivot column, which is zero in every other row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowEchelon {
+    pub rows: Vec<Vec<Rational>>,
+    pub rhs: Vec<Rational>,
+    pub pivots: Vec<usize>
+}
+
+// Gauss-Jordan elimination of A x = b. Equations that follow from the others
+// are dropped, so the rows that remain are independent. None if the
+// equations contradict each other.
+pub fn row_reduce(a: &[Vec<Rational>], b: &[Rational]) -> Result<Option<RowEchelon>, Overflow> {
+    let mut rows = a.to_vec();
+    let mut rhs = b.to_vec();
+    let num_cols = rows.first().map_or(0, |row| row.len());
+    let mut pivots = Vec::new();
+    for col in 0..num_cols {
+        let r = pivots.len();
+        let Some(pivot) = (r..rows.len()).find(|&i| !rows[i][col].is_zero()) else { continue; };
+        rows.swap(r, pivot);
+        rhs.swap(r, pivot);
+        let scale = rows[r][col];
+        for v in rows[r].iter_mut() {
+            *v = v.checked_div(scale)?;
+        }
+        rhs[r] = rhs[r].checked_div(scale)?;
+        let (pivot_row, pivot_rhs) = (rows[r].clone(), rhs[r]);
+        for (i, (row, value)) in rows.iter_mut().zip(rhs.iter_mut()).enumerate() {
+            let factor = row[col];
+            if i != r && !factor.is_zero() {
+                for (v, &p) in row.iter_mut().zip(&pivot_row) {
+                    *v = v.checked_sub(factor.checked_mul(p)?)?;
+                }
+                *value = value.checked_sub(factor.checked_mul(pivot_rhs)?)?;
+            }
+        }
+        pivots.push(col);
+    }
+    // What's left below the pivots reads 0 = rhs.
+    if rhs[pivots.len()..].iter().any(|v| !v.is_zero()) {
+        return Ok(None);
+    }
+    rows.truncate(pivots.len());
+    rhs.truncate(pivots.len());
+    Ok(Some(RowEchelon { rows, rhs, pivots }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rationals(values: &[i64]) -> Vec<Rational> {
+        values.iter().map(|&v| Rational::from(v)).collect()
+    }
+
+    #[test]
+    fn reduces_to_independent_rows() {
+        // The third equation is the sum of the first two.
+        let a = [rationals(&[1, 2, 1]), rationals(&[2, 0, 1]), rationals(&[3, 2, 2])];
+        let echelon = row_reduce(&a, &rationals(&[4, 3, 7])).unwrap().unwrap();
+        assert_eq!(echelon.pivots, [0, 1]);
+        assert_eq!(echelon.rows, [
+            vec![Rational::ONE, Rational::ZERO, Rational::new(1, 2)],
+            vec![Rational::ZERO, Rational::ONE, Rational::new(1, 4)]
+        ]);
+        assert_eq!(echelon.rhs, [Rational::new(3, 2), Rational::new(5, 4)]);
+    }
+
+    #[test]
+    fn inconsistent() {
+        let a = [rationals(&[1, 1]), rationals(&[2, 2])];
+        assert_eq!(row_reduce(&a, &rationals(&[1, 3])), Ok(None));
+    }
+}
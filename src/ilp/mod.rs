@@ -0,0 +1,275 @@
+// Exact integer linear programming. Ilp::minimise finds an integer point
+// minimising a linear objective subject to linear equations and bounds on
+// each variable. Equations are first reduced by Gaussian elimination over the
+// rationals, dropping redundant ones and rejecting contradictory ones. Branch
+// and bound then solves the linear relaxation with an exact simplex method,
+// splitting on a variable whose relaxed value is fractional until every branch
+// is either integral or can't beat the best integral point found. All
+// arithmetic is exact, so when the search finishes the answer is proven
+// optimal.
+//
+//     // Minimise a + b with 2a + 3b = 12.
+//     let mut ilp = Ilp::new(vec![1, 1]);
+//     ilp.add_equation(vec![2, 3], 12);
+//     let Outcome::Optimal(solution) = ilp.minimise().outcome else { panic!() };
+//     assert_eq!((solution.value, solution.values), (4, vec![0, 4]));
+
+mod gauss;
+mod rational;
+mod simplex;
+
+pub use gauss::{row_reduce, RowEchelon};
+pub use rational::{Overflow, Rational};
+pub use simplex::{minimise as minimise_lp, LpResult, LpSolution};
+
+// Minimise objective . x subject to the equations and bounds, with every
+// variable an integer. Branch and bound only has finitely many branches to
+// try when each variable is bounded, by set_bounds or by the equations
+// themselves. If a variable is unbounded and there is no integer solution,
+// e.g. 2a - 2b = 1, minimise never finishes.
+#[derive(Debug, Clone)]
+pub struct Ilp {
+    objective: Vec<i64>,
+    equations: Vec<(Vec<i64>, i64)>,
+    lower: Vec<i64>,
+    upper: Vec<Option<i64>>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IlpSolution {
+    pub value: i64,
+    pub values: Vec<i64>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Optimal(IlpSolution),
+    Infeasible,
+    // The linear relaxation is unbounded, so there may be no optimum.
+    Unbounded,
+    // The exact arithmetic outgrew i128 fractions, or a value outgrew i64.
+    Overflow
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    // Branch and bound nodes whose relaxation was solved.
+    pub nodes: usize,
+    // Nodes discarded as infeasible or unable to beat the best solution.
+    pub pruned: usize,
+    // Times an integral solution improved on the best so far.
+    pub improvements: usize,
+    // Simplex pivots over all nodes.
+    pub pivots: usize
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IlpResult {
+    pub outcome: Outcome,
+    pub stats: Stats
+}
+
+// A subproblem: the original with tightened bounds.
+struct Node {
+    lower: Vec<i64>,
+    upper: Vec<Option<i64>>
+}
+
+impl Ilp {
+    // A problem over objective.len() variables, each non-negative with no
+    // upper bound until set otherwise.
+    pub fn new(objective: Vec<i64>) -> Self {
+        let n = objective.len();
+        Ilp { objective, equations: Vec::new(), lower: vec![0; n], upper: vec![None; n] }
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.objective.len()
+    }
+
+    // Requires coefficients . x = rhs.
+    pub fn add_equation(&mut self, coefficients: Vec<i64>, rhs: i64) {
+        assert_eq!(coefficients.len(), self.num_vars());
+        self.equations.push((coefficients, rhs));
+    }
+
+    // Requires lower <= x[var] <= upper, with no upper bound for None.
+    pub fn set_bounds(&mut self, var: usize, lower: i64, upper: Option<i64>) {
+        self.lower[var] = lower;
+        self.upper[var] = upper;
+    }
+
+    pub fn minimise(&self) -> IlpResult {
+        let mut stats = Stats::default();
+        let outcome = self.branch_and_bound(&mut stats).unwrap_or(Outcome::Overflow);
+        IlpResult { outcome, stats }
+    }
+
+    fn branch_and_bound(&self, stats: &mut Stats) -> Result<Outcome, Overflow> {
+        let a: Vec<Vec<Rational>> = self.equations.iter().map(|(row, _)| row.iter().map(|&v| Rational::from(v)).collect()).collect();
+        let b: Vec<Rational> = self.equations.iter().map(|&(_, rhs)| Rational::from(rhs)).collect();
+        let Some(echelon) = row_reduce(&a, &b)? else { return Ok(Outcome::Infeasible); };
+        let to_i64 = |value: i128| i64::try_from(value).map_err(|_| Overflow);
+        let mut best: Option<IlpSolution> = None;
+        let mut stack = vec![Node { lower: self.lower.clone(), upper: self.upper.clone() }];
+        while let Some(node) = stack.pop() {
+            if node.lower.iter().zip(&node.upper).any(|(&low, &high)| high.is_some_and(|high| low > high)) {
+                stats.pruned += 1;
+                continue;
+            }
+            stats.nodes += 1;
+            let LpSolution { value, x, .. } = match self.relaxation(&echelon, &node, stats)? {
+                LpResult::Optimal(solution) => solution,
+                LpResult::Infeasible => {
+                    stats.pruned += 1;
+                    continue;
+                }
+                LpResult::Unbounded => return Ok(Outcome::Unbounded)
+            };
+            // The objective is integral at integer points, so a relaxation
+            // that rounds up to the best value found can't improve on it.
+            if best.as_ref().is_some_and(|best| value.ceil() >= best.value as i128) {
+                stats.pruned += 1;
+                continue;
+            }
+            match x.iter().position(|v| !v.is_integer()) {
+                None => {
+                    stats.improvements += 1;
+                    let values = x.iter().map(|v| to_i64(v.floor())).collect::<Result<Vec<i64>, Overflow>>()?;
+                    best = Some(IlpSolution { value: to_i64(value.floor())?, values });
+                }
+                Some(var) => {
+                    // Explore rounding down first by pushing it last.
+                    let mut up = Node { lower: node.lower.clone(), upper: node.upper.clone() };
+                    up.lower[var] = to_i64(x[var].ceil())?;
+                    let mut down = node;
+                    down.upper[var] = Some(to_i64(x[var].floor())?);
+                    stack.push(up);
+                    stack.push(down);
+                }
+            }
+        }
+        Ok(best.map_or(Outcome::Infeasible, Outcome::Optimal))
+    }
+
+    // The linear relaxation of a node, returning its objective value and the
+    // values of the original variables. Each variable is shifted by its lower
+    // bound so the simplex can assume y >= 0, and each upper bound becomes an
+    // equation with a slack variable.
+    fn relaxation(&self, echelon: &RowEchelon, node: &Node, stats: &mut Stats) -> Result<LpResult, Overflow> {
+        let n = self.num_vars();
+        let bounded: Vec<usize> = (0..n).filter(|&j| node.upper[j].is_some()).collect();
+        let width = n + bounded.len();
+        let lower: Vec<Rational> = node.lower.iter().map(|&low| Rational::from(low)).collect();
+        let mut a = Vec::new();
+        let mut b = Vec::new();
+        for (row, &rhs) in echelon.rows.iter().zip(&echelon.rhs) {
+            let shift = row.iter().zip(&lower).try_fold(Rational::ZERO, |sum, (&v, &low)| sum.checked_add(v.checked_mul(low)?))?;
+            a.push(row.iter().copied().chain(std::iter::repeat_n(Rational::ZERO, bounded.len())).collect());
+            b.push(rhs.checked_sub(shift)?);
+        }
+        for (k, &j) in bounded.iter().enumerate() {
+            let mut row = vec![Rational::ZERO; width];
+            row[j] = Rational::ONE;
+            row[n + k] = Rational::ONE;
+            a.push(row);
+            let range = node.upper[j].unwrap().checked_sub(node.lower[j]).ok_or(Overflow)?;
+            b.push(Rational::from(range));
+        }
+        let c: Vec<Rational> = (0..width).map(|j| Rational::from(self.objective.get(j).copied().unwrap_or(0))).collect();
+        Ok(match simplex::minimise(&c, &a, &b)? {
+            LpResult::Optimal(solution) => {
+                stats.pivots += solution.pivots;
+                let x = (0..n).map(|j| solution.x[j].checked_add(lower[j])).collect::<Result<Vec<Rational>, Overflow>>()?;
+                let value = (0..n).try_fold(Rational::ZERO, |sum, j| sum.checked_add(c[j].checked_mul(x[j])?))?;
+                LpResult::Optimal(LpSolution { value, x, pivots: solution.pivots })
+            }
+            other => other
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn optimum(ilp: &Ilp) -> IlpSolution {
+        let Outcome::Optimal(solution) = ilp.minimise().outcome else { panic!("no optimum") };
+        solution
+    }
+
+    #[test]
+    fn fractional_relaxation_without_integer_points() {
+        // 2a + 2b - 2c is always even, so only fractional points solve it.
+        let mut ilp = Ilp::new(vec![1, 1, 0]);
+        ilp.add_equation(vec![2, 2, -2], 3);
+        (0..3).for_each(|j| ilp.set_bounds(j, 0, Some(4)));
+        assert_eq!(ilp.minimise().outcome, Outcome::Infeasible);
+    }
+
+    #[test]
+    fn branches_to_the_integer_optimum() {
+        // The relaxation puts everything on z for 31/7, but integers need five
+        // presses, e.g. y = 2 and z = 3.
+        let mut ilp = Ilp::new(vec![1, 1, 1]);
+        ilp.add_equation(vec![3, 5, 7], 31);
+        let result = ilp.minimise();
+        let Outcome::Optimal(solution) = result.outcome else { panic!("no optimum") };
+        assert_eq!(solution.value, 5);
+        assert_eq!(3 * solution.values[0] + 5 * solution.values[1] + 7 * solution.values[2], 31);
+        assert!(result.stats.nodes > 1 && result.stats.pivots > 0);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = crate::generator::Rng::new(3);
+        for _ in 0..100 {
+            let n = rng.range(1, 4) as usize;
+            let mut ilp = Ilp::new((0..n).map(|_| rng.range(0, 3) as i64).collect());
+            let equations: Vec<(Vec<i64>, i64)> = (0..rng.range(1, 2)).map(|_| {
+                ((0..n).map(|_| rng.range(0, 4) as i64 - 1).collect(), rng.range(0, 12) as i64)
+            }).collect();
+            for (coefficients, rhs) in &equations {
+                ilp.add_equation(coefficients.clone(), *rhs);
+            }
+            (0..n).for_each(|j| ilp.set_bounds(j, 0, Some(6)));
+            let mut expected: Option<i64> = None;
+            for point in 0..7u32.pow(n as u32) {
+                let x: Vec<i64> = (0..n).map(|j| (point / 7u32.pow(j as u32) % 7) as i64).collect();
+                let dot = |row: &[i64]| row.iter().zip(&x).map(|(a, b)| a * b).sum::<i64>();
+                if equations.iter().all(|(row, rhs)| dot(row) == *rhs) {
+                    let value = dot(&ilp.objective);
+                    expected = Some(expected.map_or(value, |best| best.min(value)));
+                }
+            }
+            let result = ilp.minimise().outcome;
+            match (expected, result) {
+                (None, Outcome::Infeasible) => {}
+                (Some(value), Outcome::Optimal(solution)) => assert_eq!(solution.value, value),
+                (expected, result) => panic!("expected {:?}, got {:?}", expected, result)
+            }
+        }
+    }
+
+    #[test]
+    fn bounds_and_statistics() {
+        let mut ilp = Ilp::new(vec![-1, -1]);
+        ilp.add_equation(vec![1, -1], 1);
+        ilp.set_bounds(0, 2, Some(5));
+        assert_eq!(optimum(&ilp), IlpSolution { value: -9, values: vec![5, 4] });
+        let mut unbounded = Ilp::new(vec![-1, 0]);
+        unbounded.add_equation(vec![1, -1], 0);
+        let result = unbounded.minimise();
+        assert_eq!(result.outcome, Outcome::Unbounded);
+        assert_eq!(result.stats.nodes, 1);
+    }
+
+    #[test]
+    fn overflow_is_an_outcome() {
+        // The optimum a = 2^63 doesn't fit in an i64.
+        let mut ilp = Ilp::new(vec![1, 0]);
+        ilp.add_equation(vec![1, -2], 0);
+        ilp.set_bounds(1, 1 << 62, None);
+        assert_eq!(ilp.minimise().outcome, Outcome::Overflow);
+    }
+}
@@ -0,0 +1,194 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Neg;
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+// An arithmetic result whose numerator or denominator doesn't fit in an i128.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rational arithmetic overflowed")
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+// An exact fraction, always stored in lowest terms with a positive
+// denominator so that equal values have equal representations. The numerator
+// is never i128::MIN, so negating can't overflow. Arithmetic is checked and
+// fails with Overflow rather than wrapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    // Panics if `den` is zero or the fraction in lowest terms doesn't fit.
+    pub fn new(num: i128, den: i128) -> Self {
+        Self::reduced(num, den).expect("fraction out of range")
+    }
+
+    fn reduced(num: i128, den: i128) -> Result<Self, Overflow> {
+        assert!(den != 0, "zero denominator");
+        let divisor = gcd(num.unsigned_abs(), den.unsigned_abs());
+        let magnitude = i128::try_from(num.unsigned_abs() / divisor).map_err(|_| Overflow)?;
+        let positive = i128::try_from(den.unsigned_abs() / divisor).map_err(|_| Overflow)?;
+        let num = if (num < 0) != (den < 0) { -magnitude } else { magnitude };
+        Ok(Rational { num, den: positive })
+    }
+
+    pub fn numerator(self) -> i128 {
+        self.num
+    }
+
+    pub fn denominator(self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.num == 0
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.den == 1
+    }
+
+    pub fn floor(self) -> i128 {
+        self.num.div_euclid(self.den)
+    }
+
+    pub fn ceil(self) -> i128 {
+        -(-self).floor()
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Result<Self, Overflow> {
+        // Over the lowest common denominator to keep the terms small.
+        let divisor = gcd(self.den as u128, rhs.den as u128) as i128;
+        let (a, b) = (self.den / divisor, rhs.den / divisor);
+        let num = self.num.checked_mul(b).zip(rhs.num.checked_mul(a)).and_then(|(x, y)| x.checked_add(y));
+        let den = self.den.checked_mul(b);
+        Self::reduced(num.ok_or(Overflow)?, den.ok_or(Overflow)?)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, Overflow> {
+        self.checked_add(-rhs)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, Overflow> {
+        // Cancel common factors before multiplying.
+        let g1 = gcd(self.num.unsigned_abs(), rhs.den as u128) as i128;
+        let g2 = gcd(rhs.num.unsigned_abs(), self.den as u128) as i128;
+        let num = (self.num / g1).checked_mul(rhs.num / g2).ok_or(Overflow)?;
+        let den = (self.den / g2).checked_mul(rhs.den / g1).ok_or(Overflow)?;
+        Self::reduced(num, den)
+    }
+
+    // Panics if `rhs` is zero.
+    pub fn checked_div(self, rhs: Self) -> Result<Self, Overflow> {
+        assert!(!rhs.is_zero(), "division by zero");
+        let sign = rhs.num.signum();
+        self.checked_mul(Rational { num: sign * rhs.den, den: sign * rhs.num })
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Rational { num: value as i128, den: 1 }
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Rational { num: -self.num, den: self.den }
+    }
+}
+
+impl Ord for Rational {
+    // Compares integer parts, then the fractional parts by comparing their
+    // reciprocals the other way round, so nothing is multiplied and nothing
+    // can overflow.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (self.floor(), other.floor());
+        if a != b {
+            return a.cmp(&b);
+        }
+        let (r, s) = (self.num.rem_euclid(self.den), other.num.rem_euclid(other.den));
+        if r == 0 || s == 0 {
+            return r.cmp(&s);
+        }
+        Rational { num: other.den, den: s }.cmp(&Rational { num: self.den, den: r })
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 { write!(f, "{}", self.num) } else { write!(f, "{}/{}", self.num, self.den) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(num: i128, den: i128) -> Rational {
+        Rational::new(num, den)
+    }
+
+    #[test]
+    fn lowest_terms() {
+        assert_eq!(r(4, -6), r(-2, 3));
+        assert_eq!((r(4, -6).numerator(), r(4, -6).denominator()), (-2, 3));
+        assert_eq!(r(0, -5), Rational::ZERO);
+        assert_eq!(r(-3, 7).to_string(), "-3/7");
+        assert_eq!(Rational::from(5).to_string(), "5");
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(r(1, 2).checked_add(r(1, 3)), Ok(r(5, 6)));
+        assert_eq!(r(1, 2).checked_sub(r(3, 4)), Ok(r(-1, 4)));
+        assert_eq!(r(2, 3).checked_mul(r(9, 4)), Ok(r(3, 2)));
+        assert_eq!(r(2, 3).checked_div(r(-4, 9)), Ok(r(-3, 2)));
+        assert!(r(1, 3) < r(1, 2) && r(-1, 2) < r(-1, 3));
+        assert!(r(7, 3) > r(9, 4) && r(-7, 3) < r(-9, 4) && r(5, 2) == r(10, 4));
+    }
+
+    #[test]
+    fn overflow() {
+        let big = Rational::from(i64::MAX).checked_mul(Rational::from(i64::MAX)).unwrap();
+        assert_eq!(big.checked_mul(Rational::from(4)), Err(Overflow));
+        assert_eq!(big.checked_add(big).and_then(|sum| sum.checked_add(big)), Err(Overflow));
+        assert_eq!(r(1, i128::MAX).checked_add(r(1, i128::MAX - 1)), Err(Overflow));
+        // Large terms that cancel still work.
+        assert_eq!(r(i128::MAX, 3).checked_mul(r(3, i128::MAX)), Ok(Rational::ONE));
+        assert_eq!(r(1, i128::MAX).checked_sub(r(1, i128::MAX)), Ok(Rational::ZERO));
+        // Comparing doesn't multiply, so it works for any values.
+        assert!(r(i128::MAX, i128::MAX - 1) < r(i128::MAX - 1, i128::MAX - 2));
+        assert!(r(i128::MAX - 1, i128::MAX) < r(i128::MAX, i128::MAX - 1));
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq!((r(7, 2).floor(), r(7, 2).ceil()), (3, 4));
+        assert_eq!((r(-7, 2).floor(), r(-7, 2).ceil()), (-4, -3));
+        assert_eq!((r(6, 2).floor(), r(6, 2).ceil()), (3, 3));
+        assert!(r(6, 2).is_integer() && !r(7, 2).is_integer());
+    }
+}
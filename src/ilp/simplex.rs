@@ -0,0 +1,167 @@
+use super::{Overflow, Rational};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LpResult {
+    Optimal(LpSolution),
+    Infeasible,
+    Unbounded
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LpSolution {
+    pub value: Rational,
+    pub x: Vec<Rational>,
+    // The number of pivots taken to find it.
+    pub pivots: usize
+}
+
+// A dictionary for the simplex method. Each row expresses its basic variable
+// in terms of the non-basic ones, with the right hand side last, and the
+// objective row holds the reduced costs followed by minus the objective value.
+struct Tableau {
+    rows: Vec<Vec<Rational>>,
+    basis: Vec<usize>,
+    objective: Vec<Rational>,
+    pivots: usize
+}
+
+impl Tableau {
+    fn rhs(&self) -> usize {
+        self.objective.len() - 1
+    }
+
+    // Makes column `col` basic in row `r`.
+    fn pivot(&mut self, r: usize, col: usize) -> Result<(), Overflow> {
+        let scale = self.rows[r][col];
+        let pivot_row = self.rows[r].iter().map(|&v| v.checked_div(scale)).collect::<Result<Vec<Rational>, Overflow>>()?;
+        let others = self.rows.iter_mut().enumerate().filter(|&(i, _)| i != r).map(|(_, row)| row);
+        for row in others.chain(std::iter::once(&mut self.objective)) {
+            let factor = row[col];
+            if !factor.is_zero() {
+                for (v, &p) in row.iter_mut().zip(&pivot_row) {
+                    *v = v.checked_sub(factor.checked_mul(p)?)?;
+                }
+            }
+        }
+        self.rows[r] = pivot_row;
+        self.basis[r] = col;
+        self.pivots += 1;
+        Ok(())
+    }
+
+    // Pivots until no column below `num_cols` can improve the objective. Bland's
+    // rule, entering with the lowest column and leaving with the lowest basic
+    // variable on ties, guarantees this terminates. Returns false if the
+    // objective is unbounded below.
+    fn optimise(&mut self, num_cols: usize) -> Result<bool, Overflow> {
+        let rhs = self.rhs();
+        loop {
+            let Some(col) = (0..num_cols).find(|&j| self.objective[j] < Rational::ZERO) else { return Ok(true); };
+            let ratios = (0..self.rows.len()).filter(|&i| self.rows[i][col] > Rational::ZERO).map(|i| {
+                Ok((self.rows[i][rhs].checked_div(self.rows[i][col])?, self.basis[i], i))
+            }).collect::<Result<Vec<(Rational, usize, usize)>, Overflow>>()?;
+            let Some(&(_, _, r)) = ratios.iter().min() else { return Ok(false); };
+            self.pivot(r, col)?;
+        }
+    }
+}
+
+// Minimises c.x subject to A x = b and x >= 0 with the two phase simplex
+// method. Arithmetic is exact, so the result is exactly optimal, or Overflow
+// if the numbers grow too large to represent.
+pub fn minimise(c: &[Rational], a: &[Vec<Rational>], b: &[Rational]) -> Result<LpResult, Overflow> {
+    let (m, n) = (a.len(), c.len());
+    // Phase one starts from an artificial variable for each row and minimises
+    // their sum, which reaches zero exactly when A x = b has a solution.
+    let rows: Vec<Vec<Rational>> = a.iter().zip(b).enumerate().map(|(i, (coefficients, &rhs))| {
+        // Rows with a negative right hand side are negated.
+        let flip = |v: Rational| if rhs < Rational::ZERO { -v } else { v };
+        let artificial = (0..m).map(|k| if k == i { Rational::ONE } else { Rational::ZERO });
+        coefficients.iter().map(|&v| flip(v)).chain(artificial).chain([flip(rhs)]).collect()
+    }).collect();
+    let objective = (0..=n + m).map(|j| {
+        if (n..n + m).contains(&j) { Ok(Rational::ZERO) } else { rows.iter().try_fold(Rational::ZERO, |sum, row| sum.checked_sub(row[j])) }
+    }).collect::<Result<Vec<Rational>, Overflow>>()?;
+    let mut tableau = Tableau { rows, basis: (n..n + m).collect(), objective, pivots: 0 };
+    tableau.optimise(n + m)?;
+    if tableau.objective[n + m] < Rational::ZERO {
+        return Ok(LpResult::Infeasible);
+    }
+    // Artificial variables still basic are zero. Swap them for a real variable
+    // where the row has one, and otherwise the row is redundant.
+    let mut i = 0;
+    while i < tableau.rows.len() {
+        if tableau.basis[i] < n {
+            i += 1;
+        } else if let Some(col) = (0..n).find(|&j| !tableau.rows[i][j].is_zero()) {
+            tableau.pivot(i, col)?;
+            i += 1;
+        } else {
+            tableau.rows.remove(i);
+            tableau.basis.remove(i);
+        }
+    }
+    // Phase two drops the artificial columns and minimises the real objective.
+    for row in tableau.rows.iter_mut() {
+        row.drain(n..n + m);
+    }
+    let basic_cost = |j: usize, tableau: &Tableau| {
+        tableau.rows.iter().zip(&tableau.basis).try_fold(Rational::ZERO, |sum, (row, &var)| sum.checked_add(c[var].checked_mul(row[j])?))
+    };
+    let objective = (0..=n).map(|j| {
+        if j < n { c[j].checked_sub(basic_cost(j, &tableau)?) } else { Ok(-basic_cost(j, &tableau)?) }
+    }).collect::<Result<Vec<Rational>, Overflow>>()?;
+    tableau.objective = objective;
+    if !tableau.optimise(n)? {
+        return Ok(LpResult::Unbounded);
+    }
+    let mut x = vec![Rational::ZERO; n];
+    for (row, &var) in tableau.rows.iter().zip(&tableau.basis) {
+        x[var] = row[n];
+    }
+    Ok(LpResult::Optimal(LpSolution { value: -tableau.objective[n], x, pivots: tableau.pivots }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rationals(values: &[i64]) -> Vec<Rational> {
+        values.iter().map(|&v| Rational::from(v)).collect()
+    }
+
+    fn solve(c: &[i64], a: &[&[i64]], b: &[i64]) -> LpResult {
+        let a: Vec<Vec<Rational>> = a.iter().map(|row| rationals(row)).collect();
+        minimise(&rationals(c), &a, &rationals(b)).unwrap()
+    }
+
+    #[test]
+    fn optimal_vertex() {
+        // Minimise -x - y with x + 2y + s = 4, 3x + y + t = 6: the optimum is
+        // at x = 8/5, y = 6/5.
+        let LpResult::Optimal(solution) = solve(&[-1, -1, 0, 0], &[&[1, 2, 1, 0], &[3, 1, 0, 1]], &[4, 6]) else { panic!() };
+        assert_eq!(solution.value, Rational::new(-14, 5));
+        assert_eq!(solution.x, [Rational::new(8, 5), Rational::new(6, 5), Rational::ZERO, Rational::ZERO]);
+    }
+
+    #[test]
+    fn negative_rhs_and_redundant_rows() {
+        // x - y = -2 twice over, minimising x + y: x = 0, y = 2.
+        let LpResult::Optimal(solution) = solve(&[1, 1], &[&[1, -1], &[2, -2]], &[-2, -4]) else { panic!() };
+        assert_eq!(solution.value, Rational::from(2));
+        assert_eq!(solution.x, rationals(&[0, 2]));
+    }
+
+    #[test]
+    fn infeasible_and_unbounded() {
+        assert_eq!(solve(&[1, 1], &[&[1, 1], &[1, 1]], &[1, 2]), LpResult::Infeasible);
+        assert_eq!(solve(&[1, 1], &[&[1, 1]], &[-1]), LpResult::Infeasible);
+        assert_eq!(solve(&[-1, 0], &[&[1, -1]], &[0]), LpResult::Unbounded);
+    }
+
+    #[test]
+    fn no_constraints() {
+        let LpResult::Optimal(solution) = solve(&[2, 3], &[], &[]) else { panic!() };
+        assert_eq!((solution.value, solution.x), (Rational::ZERO, rationals(&[0, 0])));
+    }
+}
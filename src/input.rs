@@ -0,0 +1,167 @@
+use std::env;
+use std::fmt;
+use std::fs::read_to_string;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+// Passing this as the input path reads the puzzle input from stdin.
+pub const STDIN_PATH: &str = "-";
+
+#[derive(Debug, Clone, Default)]
+pub struct InputOptions {
+    // Explicit input file, bypassing the directory search.
+    pub path: Option<String>,
+    // Example number: 1 selects test_input.txt, 2 selects test_input2.txt, etc.
+    pub example: Option<u32>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf)
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display())
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound { day: u32, file_name: String, tried: Vec<PathBuf> },
+    Read { source: InputSource, error: io::Error }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::NotFound { day, file_name, tried } => {
+                write!(f, "No {} found for day {}, tried:", file_name, day)?;
+                for path in tried {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                write!(f, "\nSet {} or pass an input path ('{}' for stdin)", INPUT_DIR_VAR, STDIN_PATH)
+            }
+            InputError::Read { source, error } => write!(f, "Failed to read {}: {}", source, error)
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn example_file_name(example: u32) -> String {
+    if example <= 1 {
+        "test_input.txt".to_string()
+    } else {
+        format!("test_input{}.txt", example)
+    }
+}
+
+// Directories searched for inputs/<day>/<file> in order. AOC_INPUT_DIR takes
+// precedence, otherwise try the working directory and then the crate root so
+// that `cargo run` and `cargo test` work from anywhere in the tree.
+pub fn input_dirs() -> Vec<PathBuf> {
+    if let Some(dir) = env::var_os(INPUT_DIR_VAR) {
+        return vec![PathBuf::from(dir)];
+    }
+    vec![
+        PathBuf::from("inputs"),
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("inputs")
+    ]
+}
+
+pub fn find_day_dir(day: u32) -> Result<PathBuf, InputError> {
+    let tried: Vec<PathBuf> = input_dirs().into_iter().map(|dir| dir.join(day.to_string())).collect();
+    match tried.iter().find(|dir| dir.is_dir()) {
+        Some(dir) => Ok(dir.clone()),
+        None => Err(InputError::NotFound { day, file_name: "input directory".to_string(), tried })
+    }
+}
+
+// Example number of an input file name: 0 for input.txt, 1 for test_input.txt,
+// N for test_inputN.txt, or None for anything else.
+pub fn example_number(file_name: &str) -> Option<u32> {
+    if file_name == "input.txt" {
+        return Some(0);
+    }
+    let n = file_name.strip_prefix("test_input")?.strip_suffix(".txt")?;
+    if n.is_empty() { Some(1) } else { n.parse::<u32>().ok().filter(|&n| n > 1) }
+}
+
+// The input files present in a day's directory, real input first followed by
+// the examples in order.
+pub fn input_files(dir: &Path) -> Vec<String> {
+    let Ok(entries) = dir.read_dir() else { return Vec::new(); };
+    let mut files: Vec<(u32, String)> = entries.flatten().flat_map(|entry| {
+        let name = entry.file_name().into_string().ok()?;
+        example_number(&name).map(|n| (n, name))
+    }).collect();
+    files.sort();
+    files.into_iter().map(|(_, name)| name).collect()
+}
+
+impl InputOptions {
+    pub fn file_name(&self) -> String {
+        match self.example {
+            Some(example) => example_file_name(example),
+            None => "input.txt".to_string()
+        }
+    }
+
+    pub fn resolve(&self, day: u32) -> Result<InputSource, InputError> {
+        match self.path.as_deref() {
+            Some(STDIN_PATH) => return Ok(InputSource::Stdin),
+            Some(path) => return Ok(InputSource::File(PathBuf::from(path))),
+            None => {}
+        }
+        let file_name = self.file_name();
+        let tried: Vec<PathBuf> = input_dirs().into_iter().map(|dir| {
+            dir.join(day.to_string()).join(&file_name)
+        }).collect();
+        match tried.iter().find(|path| path.is_file()) {
+            Some(path) => Ok(InputSource::File(path.clone())),
+            None => Err(InputError::NotFound { day, file_name, tried })
+        }
+    }
+
+    pub fn read(&self, day: u32) -> Result<(InputSource, String), InputError> {
+        let source = self.resolve(day)?;
+        let text = match &source {
+            InputSource::Stdin => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text).map(|_| text)
+            }
+            InputSource::File(path) => read_to_string(path)
+        };
+        match text {
+            Ok(text) => Ok((source, text)),
+            Err(error) => Err(InputError::Read { source, error })
+        }
+    }
+}
+
+impl InputOptions {
+    // Applies a command line argument shared by all the binaries: `--example`,
+    // `--example=N`, or a positional input path.
+    pub fn apply_arg(&mut self, arg: &str) -> Result<(), String> {
+        if arg == "--example" {
+            self.example = Some(1);
+        } else if let Some(n) = arg.strip_prefix("--example=") {
+            let n = n.parse::<u32>().ok().filter(|&n| n > 0).ok_or(format!("Invalid example number: {}", n))?;
+            self.example = Some(n);
+        } else if arg.starts_with("--") {
+            return Err(format!("Unknown argument: {}", arg));
+        } else if self.path.is_some() {
+            return Err(format!("Unexpected argument: {}", arg));
+        } else {
+            self.path = Some(arg.to_string());
+        }
+        Ok(())
+    }
+}
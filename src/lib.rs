@@ -1 +1,18 @@
-pub mod util;
\ No newline at end of file
+pub mod util;
+pub mod input;
+pub mod parse;
+pub mod solution;
+pub mod answers;
+pub mod runner;
+pub mod output;
+pub mod pool;
+pub mod verify;
+pub mod bench;
+pub mod generator;
+pub mod differential;
+pub mod graph;
+pub mod search;
+pub mod cp;
+pub mod gf2;
+pub mod ilp;
+pub mod days;
@@ -0,0 +1,123 @@
+use crate::runner::RunResult;
+use crate::solution::Answer;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Csv
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!("Unknown output format: {} (expected text, json or csv)", s))
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c)
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(n) => n.to_string(),
+        Answer::Unavailable => "null".to_string(),
+        Answer::Unsolvable(reason) => format!("{{\"error\": {}}}", json_string(reason))
+    }
+}
+
+fn csv_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(n) => n.to_string(),
+        Answer::Unavailable => String::new(),
+        Answer::Unsolvable(_) => csv_field(&answer.to_string())
+    }
+}
+
+pub fn format_results(results: &[RunResult], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Text => results.iter().map(|result| {
+            format!("Day {} part {}: {}\n", result.day, result.part, result.answer)
+        }).collect(),
+        OutputFormat::Json => {
+            let objects: Vec<String> = results.iter().map(|result| {
+                format!("  {{\"day\": {}, \"part\": {}, \"input\": {}, \"answer\": {}, \"elapsed_ns\": {}}}",
+                    result.day, result.part, json_string(&result.input), json_answer(&result.answer), result.elapsed.as_nanos())
+            }).collect();
+            if objects.is_empty() { "[]\n".to_string() } else { format!("[\n{}\n]\n", objects.join(",\n")) }
+        }
+        OutputFormat::Csv => {
+            let mut out = "day,part,input,answer,elapsed_ns\n".to_string();
+            for result in results {
+                out += &format!("{},{},{},{},{}\n", result.day, result.part, csv_field(&result.input),
+                    csv_answer(&result.answer), result.elapsed.as_nanos());
+            }
+            out
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Part;
+    use std::time::Duration;
+
+    fn results() -> Vec<RunResult> {
+        vec![
+            RunResult { day: 1, part: Part::One, input: "inputs/1/input.txt".to_string(), answer: Answer::Number(1141), elapsed: Duration::from_nanos(1500) },
+            RunResult { day: 12, part: Part::Two, input: "a \"b\", c".to_string(), answer: Answer::Unavailable, elapsed: Duration::from_nanos(7) },
+            RunResult { day: 10, part: Part::One, input: "-".to_string(), answer: Answer::Unsolvable("machine 2, x".to_string()), elapsed: Duration::from_nanos(3) },
+        ]
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(format_results(&results(), OutputFormat::Json), concat!(
+            "[\n",
+            "  {\"day\": 1, \"part\": 1, \"input\": \"inputs/1/input.txt\", \"answer\": 1141, \"elapsed_ns\": 1500},\n",
+            "  {\"day\": 12, \"part\": 2, \"input\": \"a \\\"b\\\", c\", \"answer\": null, \"elapsed_ns\": 7},\n",
+            "  {\"day\": 10, \"part\": 1, \"input\": \"-\", \"answer\": {\"error\": \"machine 2, x\"}, \"elapsed_ns\": 3}\n",
+            "]\n"));
+        assert_eq!(format_results(&[], OutputFormat::Json), "[]\n");
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(format_results(&results(), OutputFormat::Csv), concat!(
+            "day,part,input,answer,elapsed_ns\n",
+            "1,1,inputs/1/input.txt,1141,1500\n",
+            "12,2,\"a \"\"b\"\", c\",,7\n",
+            "10,1,-,\"no answer: machine 2, x\",3\n"));
+    }
+}
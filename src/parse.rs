@@ -0,0 +1,218 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>, found: impl Into<String>) -> Self {
+        ParseError { file: None, line, column, expected: expected.into(), found: found.into() }
+    }
+
+    pub fn in_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = self.file.as_deref().unwrap_or("<input>");
+        write!(f, "{}:{}:{}: expected {}, found {}", file, self.line, self.column, self.expected, self.found)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn describe(text: &str) -> String {
+    if text.is_empty() { "nothing".to_string() } else { format!("'{}'", text) }
+}
+
+// A line of puzzle input together with its 1-based line number. Errors are
+// reported against sub-slices of the line so that the column is exact.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str
+}
+
+impl<'a> Line<'a> {
+    // 1-based column of `part`, which must be a sub-slice of this line.
+    pub fn column_of(&self, part: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize).wrapping_sub(start);
+        if offset <= self.text.len() {
+            self.text[..offset].chars().count() + 1
+        } else {
+            1
+        }
+    }
+
+    // The character starting at byte `index` as a sub-slice, for reporting errors.
+    pub fn char_at(&self, index: usize) -> &'a str {
+        first_char(&self.text[index..])
+    }
+
+    pub fn error(&self, part: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, self.column_of(part), expected, describe(part))
+    }
+
+    pub fn error_at_end(&self, expected: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, self.text.chars().count() + 1, expected, "end of line")
+    }
+
+    pub fn parse<T: FromStr>(&self, part: &str, expected: impl Into<String>) -> Result<T, ParseError> {
+        part.parse::<T>().map_err(|_| self.error(part, expected))
+    }
+
+    // Every integer in the line, ignoring whatever separates them. A '-' is
+    // taken as a sign unless it directly follows a digit, so "3-5" is 3 and 5.
+    pub fn integers<T: FromStr>(&self) -> Result<Vec<T>, ParseError> {
+        let bytes = self.text.as_bytes();
+        let mut integers = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            let negative = bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) &&
+                (i == 0 || !bytes[i - 1].is_ascii_digit());
+            if !negative && !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            integers.push(self.parse(&self.text[start..i], "an integer")?);
+        }
+        Ok(integers)
+    }
+
+    // Splits `text`, a sub-slice of this line, on `separator` and parses each
+    // whitespace-trimmed item. Empty text is an empty list.
+    pub fn list<T>(&self, text: &'a str, separator: &str, item: impl FnMut(&'a str) -> Result<T, ParseError>) -> Result<Vec<T>, ParseError> {
+        if text.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        text.split(separator).map(str::trim).map(item).collect()
+    }
+
+    // Splits `text`, a sub-slice of this line, into the contents of the group
+    // it starts with, such as "(0,2)", and the text after it. Nested groups with
+    // the same brackets are skipped over.
+    pub fn bracketed(&self, text: &'a str, open: char, close: char) -> Result<(&'a str, &'a str), ParseError> {
+        let text = text.trim_start();
+        let Some(inner) = text.strip_prefix(open) else { return Err(self.error(first_char(text), format!("'{}'", open))); };
+        let mut depth = 0;
+        for (i, c) in inner.char_indices() {
+            if c == close && depth == 0 {
+                return Ok((&inner[..i], &inner[i + c.len_utf8()..]));
+            } else if c == close {
+                depth -= 1;
+            } else if c == open {
+                depth += 1;
+            }
+        }
+        Err(self.error_at_end(format!("'{}'", close)))
+    }
+
+    // Splits a "key: a b c" line into its trimmed key and whitespace separated
+    // values.
+    pub fn key_values(&self) -> Result<(&'a str, Vec<&'a str>), ParseError> {
+        let Some((key, values)) = self.text.split_once(':') else { return Err(self.error_at_end("':'")); };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(self.error(key, "a key before ':'"));
+        }
+        Ok((key, values.split_whitespace().collect()))
+    }
+}
+
+// The first character of `text` as a sub-slice, or an empty slice at the end.
+pub fn first_char(text: &str) -> &str {
+    let len = text.chars().next().map_or(0, char::len_utf8);
+    &text[..len]
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item=Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line { number: i + 1, text })
+}
+
+// Groups the lines of the input into sections separated by blank lines.
+pub fn sections(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut sections = vec![Vec::new()];
+    for line in lines(input) {
+        if !line.text.trim().is_empty() {
+            sections.last_mut().unwrap().push(line);
+        } else if !sections.last().unwrap().is_empty() {
+            sections.push(Vec::new());
+        }
+    }
+    sections.retain(|section| !section.is_empty());
+    sections
+}
+
+pub fn end_of_input(input: &str, expected: impl Into<String>) -> ParseError {
+    ParseError::new(input.lines().count() + 1, 1, expected, "end of input")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> Line<'_> {
+        Line { number: 3, text }
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(line("x=-5, y=12..-3").integers::<i64>(), Ok(vec![-5, 12, -3]));
+        assert_eq!(line("3-5").integers::<u64>(), Ok(vec![3, 5]));
+        assert_eq!(line("no numbers").integers::<u64>(), Ok(vec![]));
+        let err = line("1 99999999999").integers::<u32>().unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+    }
+
+    #[test]
+    fn lists() {
+        let l = line("1, 2,3");
+        assert_eq!(l.list(l.text, ",", |n| l.parse::<u32>(n, "a number")), Ok(vec![1, 2, 3]));
+        assert_eq!(l.list(&l.text[6..], ",", |n| l.parse::<u32>(n, "a number")), Ok(vec![]));
+        let l = line("1,x");
+        assert_eq!(l.list(l.text, ",", |n| l.parse::<u32>(n, "a number")).unwrap_err().column, 3);
+    }
+
+    #[test]
+    fn bracketed_groups() {
+        let l = line("[.#] (0,(1)) {3}");
+        let (lights, rest) = l.bracketed(l.text, '[', ']').unwrap();
+        assert_eq!((lights, rest), (".#", " (0,(1)) {3}"));
+        let (button, rest) = l.bracketed(rest, '(', ')').unwrap();
+        assert_eq!((button, rest), ("0,(1)", " {3}"));
+        assert_eq!(l.bracketed(rest, '(', ')').unwrap_err().column, 14);
+        assert_eq!(l.bracketed("{3", '{', '}').unwrap_err().found, "end of line");
+    }
+
+    #[test]
+    fn key_values() {
+        assert_eq!(line("aaa: bbb ccc").key_values(), Ok(("aaa", vec!["bbb", "ccc"])));
+        assert_eq!(line("0:").key_values(), Ok(("0", vec![])));
+        assert!(line("aaa bbb").key_values().is_err());
+        assert!(line(": bbb").key_values().is_err());
+    }
+
+    #[test]
+    fn blank_line_sections() {
+        let input = "a\nb\n\n\nc\n\n";
+        let sections: Vec<Vec<(usize, &str)>> = sections(input).iter().map(|section| {
+            section.iter().map(|line| (line.number, line.text)).collect()
+        }).collect();
+        assert_eq!(sections, [vec![(1, "a"), (2, "b")], vec![(5, "c")]]);
+    }
+}
@@ -0,0 +1,217 @@
+use crate::parse::ParseError;
+use crate::runner::RunResult;
+use crate::solution::{DynSolution, Part};
+use std::collections::HashMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// One part of one day run against one input. The input text is shared between
+// the jobs for each part so that it only has to be read once, which matters
+// when it comes from stdin.
+#[derive(Clone)]
+pub struct Job {
+    pub solution: &'static dyn DynSolution,
+    pub part: Part,
+    pub input_name: Arc<str>,
+    pub input: Arc<str>,
+    // Whether the input is one of the puzzle's examples.
+    pub example: bool
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Finished(RunResult),
+    Failed(String),
+    TimedOut(Duration)
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn run_job(job: &Job) -> Outcome {
+    let result = catch_unwind(AssertUnwindSafe(|| -> Result<RunResult, ParseError> {
+        let parsed = job.solution.parse_input(&job.input, job.example).map_err(|err| err.in_file(job.input_name.to_string()))?;
+        let start = Instant::now();
+        let answer = job.solution.solve(parsed.as_ref(), job.part);
+        Ok(RunResult { day: job.solution.day(), part: job.part, input: job.input_name.to_string(), answer, elapsed: start.elapsed() })
+    }));
+    match result {
+        Ok(Ok(result)) => Outcome::Finished(result),
+        Ok(Err(err)) => Outcome::Failed(err.to_string()),
+        Err(payload) => Outcome::Failed(format!("panicked: {}", panic_message(payload.as_ref())))
+    }
+}
+
+enum Message {
+    Started(usize, Instant),
+    Finished(usize, Outcome)
+}
+
+// Runs the jobs on `workers` threads and returns their outcomes in job order.
+// `report` is called with each outcome as soon as it and every earlier job
+// have completed, so output stays ordered while later jobs keep running.
+//
+// Each worker takes the next job from a shared queue until none are left. A
+// job still running after `timeout` is reported as timed out, but threads
+// can't be cancelled, so its worker only moves on once the job finishes. A
+// job that never finishes holds on to its worker until the process exits, and
+// the remaining jobs share the other workers.
+pub fn run_jobs(jobs: &[Job], workers: usize, timeout: Option<Duration>, mut report: impl FnMut(&Job, &Outcome)) -> Vec<Outcome> {
+    let queue: Arc<[Job]> = Arc::from(jobs);
+    let next_job = Arc::new(AtomicUsize::new(0));
+    let (sender, receiver) = channel::<Message>();
+    for _ in 0..workers.clamp(1, jobs.len().max(1)) {
+        let (queue, next_job, sender) = (queue.clone(), next_job.clone(), sender.clone());
+        thread::spawn(move || {
+            loop {
+                let i = next_job.fetch_add(1, Ordering::SeqCst);
+                let Some(job) = queue.get(i) else { break; };
+                // The receiver goes away once every job has an outcome, which
+                // may happen while this one runs on after timing out.
+                if sender.send(Message::Started(i, Instant::now())).is_err() {
+                    break;
+                }
+                let outcome = run_job(job);
+                if sender.send(Message::Finished(i, outcome)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    let mut outcomes: Vec<Option<Outcome>> = vec![None; jobs.len()];
+    let mut running: HashMap<usize, Instant> = HashMap::new();
+    let mut next_report = 0;
+    while next_report < jobs.len() {
+        let message = match (timeout, running.values().min()) {
+            (Some(timeout), Some(&earliest)) => {
+                let wait = (earliest + timeout).saturating_duration_since(Instant::now());
+                receiver.recv_timeout(wait)
+            }
+            // Nothing to time out, so wait for a worker to start or finish a
+            // job.
+            _ => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+        };
+        match message {
+            Ok(Message::Started(i, start)) => {
+                running.insert(i, start);
+            }
+            Ok(Message::Finished(i, outcome)) => {
+                if running.remove(&i).is_some() {
+                    outcomes[i] = Some(outcome);
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let timeout = timeout.unwrap();
+                let expired: Vec<usize> = running.iter().filter(|(_, start)| start.elapsed() >= timeout).map(|(&i, _)| i).collect();
+                for i in expired {
+                    running.remove(&i);
+                    outcomes[i] = Some(Outcome::TimedOut(timeout));
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => unreachable!("workers run until every job has an outcome")
+        }
+        while let Some(Some(outcome)) = outcomes.get(next_report) {
+            report(&jobs[next_report], outcome);
+            next_report += 1;
+        }
+    }
+    outcomes.into_iter().map(|outcome| outcome.unwrap()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{day1::Day1, day5::Day5};
+    use crate::solution::{Answer, Solution};
+
+    struct Sleepy;
+
+    impl Solution for Sleepy {
+        const DAY: u32 = 0;
+        type Input = ();
+
+        fn parse(&self, _input: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+        fn part1(&self, _input: &Self::Input) -> Answer {
+            thread::sleep(Duration::from_millis(300));
+            Answer::Number(0)
+        }
+    }
+
+    struct Panicky;
+
+    impl Solution for Panicky {
+        const DAY: u32 = 0;
+        type Input = ();
+
+        fn parse(&self, _input: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+        fn part1(&self, _input: &Self::Input) -> Answer {
+            panic!("out of batteries")
+        }
+    }
+
+    fn job(solution: &'static dyn DynSolution, part: Part, input: &str) -> Job {
+        Job { solution, part, input_name: Arc::from("test"), input: Arc::from(input), example: false }
+    }
+
+    #[test]
+    fn outcomes_are_reported_in_order() {
+        let jobs = vec![
+            job(&Day1, Part::One, "L50\nR10\nL10"),
+            job(&Day1, Part::Two, "L150"),
+            job(&Day5, Part::One, "3-5\n\n4\n9"),
+            job(&Day1, Part::One, "X"),
+        ];
+        let mut reported = Vec::new();
+        let outcomes = run_jobs(&jobs, 3, None, |job, _| reported.push(job.part));
+        assert_eq!(reported, vec![Part::One, Part::Two, Part::One, Part::One]);
+        let answers: Vec<Option<Answer>> = outcomes.iter().map(|outcome| match outcome {
+            Outcome::Finished(result) => Some(result.answer.clone()),
+            _ => None
+        }).collect();
+        assert_eq!(answers, vec![Some(Answer::Number(2)), Some(Answer::Number(2)), Some(Answer::Number(1)), None]);
+        assert!(matches!(&outcomes[3], Outcome::Failed(message) if message.contains("expected 'L' or 'R'")));
+    }
+
+    #[test]
+    fn panics_are_failures() {
+        let outcomes = run_jobs(&[job(&Panicky, Part::One, "")], 1, None, |_, _| {});
+        assert!(matches!(&outcomes[0], Outcome::Failed(message) if message == "panicked: out of batteries"));
+    }
+
+    #[test]
+    fn slow_jobs_time_out() {
+        let jobs = vec![job(&Sleepy, Part::One, ""), job(&Day1, Part::One, "L50")];
+        let start = Instant::now();
+        let outcomes = run_jobs(&jobs, 2, Some(Duration::from_millis(50)), |_, _| {});
+        assert!(start.elapsed() < Duration::from_millis(250));
+        assert_eq!(outcomes[0], Outcome::TimedOut(Duration::from_millis(50)));
+        assert!(matches!(&outcomes[1], Outcome::Finished(result) if result.answer == Answer::Number(1)));
+    }
+
+    #[test]
+    fn timed_out_jobs_keep_their_worker() {
+        // The only worker is still busy with the sleeper after it times out,
+        // so the next job waits for it rather than starting another thread.
+        let jobs = vec![job(&Sleepy, Part::One, ""), job(&Day1, Part::One, "L50")];
+        let start = Instant::now();
+        let outcomes = run_jobs(&jobs, 1, Some(Duration::from_millis(50)), |_, _| {});
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        assert_eq!(outcomes[0], Outcome::TimedOut(Duration::from_millis(50)));
+        assert!(matches!(&outcomes[1], Outcome::Finished(result) if result.answer == Answer::Number(1)));
+    }
+}
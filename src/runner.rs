@@ -0,0 +1,84 @@
+use crate::input::{InputError, InputOptions};
+use crate::output::{format_results, OutputFormat};
+use crate::parse::ParseError;
+use crate::solution::{Answer, DynSolution, Part};
+use std::env;
+use std::fmt;
+use std::process::exit;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub enum RunError {
+    Input(InputError),
+    Parse(ParseError)
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::Input(err) => write!(f, "{}", err),
+            RunError::Parse(err) => write!(f, "{}", err)
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+impl From<InputError> for RunError {
+    fn from(err: InputError) -> Self {
+        RunError::Input(err)
+    }
+}
+
+impl From<ParseError> for RunError {
+    fn from(err: ParseError) -> Self {
+        RunError::Parse(err)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunResult {
+    pub day: u32,
+    pub part: Part,
+    pub input: String,
+    pub answer: Answer,
+    // Time spent solving the part, excluding parsing.
+    pub elapsed: Duration
+}
+
+pub fn run_parts(solution: &dyn DynSolution, input: &str, example: bool, parts: &[Part]) -> Result<Vec<(Part, Answer, Duration)>, ParseError> {
+    let parsed = solution.parse_input(input, example)?;
+    Ok(parts.iter().map(|&part| {
+        let start = Instant::now();
+        let answer = solution.solve(parsed.as_ref(), part);
+        (part, answer, start.elapsed())
+    }).collect())
+}
+
+pub fn run_day(solution: &dyn DynSolution, parts: &[Part], options: &InputOptions) -> Result<Vec<RunResult>, RunError> {
+    let (source, input) = options.read(solution.day())?;
+    let input_name = source.to_string();
+    let answers = run_parts(solution, &input, options.example.is_some(), parts).map_err(|err| err.in_file(input_name.clone()))?;
+    Ok(answers.into_iter().map(|(part, answer, elapsed)| {
+        RunResult { day: solution.day(), part, input: input_name.clone(), answer, elapsed }
+    }).collect())
+}
+
+// Entry point for the per-day binaries in src/bin.
+pub fn run_main(solution: &dyn DynSolution) {
+    let mut options = InputOptions::default();
+    for arg in env::args().skip(1) {
+        if let Err(message) = options.apply_arg(&arg) {
+            eprintln!("{}", message);
+            eprintln!("usage: {} [--example[=N]] [PATH|-]", solution.day());
+            exit(2);
+        }
+    }
+    match run_day(solution, &Part::ALL, &options) {
+        Ok(results) => print!("{}", format_results(&results, OutputFormat::Text)),
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+// Generic state-space search. States are explored through a successor
+// function yielding (action, state) pairs, or (action, state, cost) triples
+// for the weighted searches, and the result records the actions taken so that
+// callers can recover e.g. which buttons were pressed.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    // States taken off the frontier and passed to the successor function.
+    pub expanded: usize,
+    // Successors returned, including ones already seen.
+    pub generated: usize,
+    // Distinct states discovered.
+    pub discovered: usize,
+    pub max_frontier: usize
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, A, C> {
+    pub cost: C,
+    // Every state from the start to the goal, inclusive.
+    pub states: Vec<S>,
+    // The action leading to each state after the first.
+    pub actions: Vec<A>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<S, A, C> {
+    // None if no goal state is reachable.
+    pub path: Option<Path<S, A, C>>,
+    pub stats: Stats
+}
+
+// The states discovered so far, each with how it was first or best reached.
+struct Nodes<S, A, C> {
+    states: Vec<S>,
+    indices: HashMap<S, usize>,
+    parents: Vec<Option<(usize, A)>>,
+    costs: Vec<C>
+}
+
+impl<S: Clone + Eq + Hash, A: Clone, C: Copy> Nodes<S, A, C> {
+    fn new(start: S, cost: C) -> Self {
+        Nodes { indices: HashMap::from([(start.clone(), 0)]), states: vec![start], parents: vec![None], costs: vec![cost] }
+    }
+
+    // The index of `state` and whether it was new.
+    fn insert(&mut self, state: S, parent: usize, action: A, cost: C) -> (usize, bool) {
+        match self.indices.entry(state) {
+            Entry::Occupied(entry) => (*entry.get(), false),
+            Entry::Vacant(entry) => {
+                let index = self.states.len();
+                self.states.push(entry.key().clone());
+                entry.insert(index);
+                self.parents.push(Some((parent, action)));
+                self.costs.push(cost);
+                (index, true)
+            }
+        }
+    }
+
+    fn path(&self, goal: usize) -> Path<S, A, C> {
+        let mut states = vec![self.states[goal].clone()];
+        let mut actions = Vec::new();
+        let mut node = goal;
+        while let Some((parent, action)) = &self.parents[node] {
+            states.push(self.states[*parent].clone());
+            actions.push(action.clone());
+            node = *parent;
+        }
+        states.reverse();
+        actions.reverse();
+        Path { cost: self.costs[goal], states, actions }
+    }
+}
+
+// Breadth-first search for the goal state needing the fewest actions.
+pub fn bfs<S, A, I>(start: S, mut successors: impl FnMut(&S) -> I, mut is_goal: impl FnMut(&S) -> bool) -> SearchResult<S, A, usize>
+where
+    S: Clone + Eq + Hash,
+    A: Clone,
+    I: IntoIterator<Item=(A, S)>,
+{
+    let mut stats = Stats { discovered: 1, max_frontier: 1, ..Stats::default() };
+    if is_goal(&start) {
+        return SearchResult { path: Some(Path { cost: 0, states: vec![start], actions: vec![] }), stats };
+    }
+    let mut nodes = Nodes::new(start, 0);
+    let mut frontier = VecDeque::from([0]);
+    while let Some(index) = frontier.pop_front() {
+        stats.expanded += 1;
+        let cost = nodes.costs[index] + 1;
+        let state = nodes.states[index].clone();
+        for (action, next) in successors(&state) {
+            stats.generated += 1;
+            let goal = is_goal(&next);
+            let (next, new) = nodes.insert(next, index, action, cost);
+            if new {
+                if goal {
+                    stats.discovered = nodes.states.len();
+                    return SearchResult { path: Some(nodes.path(next)), stats };
+                }
+                frontier.push_back(next);
+            }
+        }
+        stats.max_frontier = stats.max_frontier.max(frontier.len());
+    }
+    stats.discovered = nodes.states.len();
+    SearchResult { path: None, stats }
+}
+
+// Dijkstra's algorithm for the cheapest goal state. Costs must not be negative.
+pub fn dijkstra<S, A, C, I>(start: S, successors: impl FnMut(&S) -> I, is_goal: impl FnMut(&S) -> bool) -> SearchResult<S, A, C>
+where
+    S: Clone + Eq + Hash,
+    A: Clone,
+    C: Copy + Ord + Add<Output=C> + Default,
+    I: IntoIterator<Item=(A, S, C)>,
+{
+    astar(start, successors, is_goal, |_| C::default())
+}
+
+// A* search for the cheapest goal state. The result is optimal as long as
+// `heuristic` never overestimates the remaining cost and is consistent.
+pub fn astar<S, A, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+    mut heuristic: impl FnMut(&S) -> C
+) -> SearchResult<S, A, C>
+where
+    S: Clone + Eq + Hash,
+    A: Clone,
+    C: Copy + Ord + Add<Output=C> + Default,
+    I: IntoIterator<Item=(A, S, C)>,
+{
+    let mut stats = Stats { discovered: 1, max_frontier: 1, ..Stats::default() };
+    // Entries are ordered by estimated total cost, breaking ties towards states
+    // further from the start since they are likely nearer the goal.
+    let mut frontier = BinaryHeap::from([Reverse((heuristic(&start), Reverse(C::default()), 0))]);
+    let mut nodes = Nodes::new(start, C::default());
+    let mut closed = vec![false];
+    while let Some(Reverse((_, Reverse(cost), index))) = frontier.pop() {
+        // The frontier may hold stale entries for states since reached more
+        // cheaply.
+        if closed[index] || cost > nodes.costs[index] {
+            continue;
+        }
+        let state = nodes.states[index].clone();
+        if is_goal(&state) {
+            stats.discovered = nodes.states.len();
+            return SearchResult { path: Some(nodes.path(index)), stats };
+        }
+        closed[index] = true;
+        stats.expanded += 1;
+        for (action, next, step) in successors(&state) {
+            stats.generated += 1;
+            let next_cost = cost + step;
+            let h = heuristic(&next);
+            let (next, new) = nodes.insert(next, index, action.clone(), next_cost);
+            if new {
+                closed.push(false);
+            } else if next_cost < nodes.costs[next] && !closed[next] {
+                nodes.costs[next] = next_cost;
+                nodes.parents[next] = Some((index, action));
+            } else {
+                continue;
+            }
+            frontier.push(Reverse((next_cost + h, Reverse(next_cost), next)));
+        }
+        stats.max_frontier = stats.max_frontier.max(frontier.len());
+    }
+    stats.discovered = nodes.states.len();
+    SearchResult { path: None, stats }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Steps on a 20x20 grid with a wall across most of row 10. Moving right or
+    // down costs 1 and moving left or up costs 2.
+    fn steps(&(x, y): &(i32, i32)) -> Vec<(char, (i32, i32), u32)> {
+        let moves = [('R', (x + 1, y), 1), ('D', (x, y + 1), 1), ('L', (x - 1, y), 2), ('U', (x, y - 1), 2)];
+        moves.into_iter().filter(|&(_, (x, y), _)| (0..20).contains(&x) && (0..20).contains(&y) && !(y == 10 && x > 2)).collect()
+    }
+
+    #[test]
+    fn bfs_finds_fewest_actions() {
+        let result = bfs(1, |&n| [('+', n + 1), ('*', n * 2)], |&n| n == 10);
+        let path = result.path.unwrap();
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.actions, ['+', '*', '+', '*']);
+        assert_eq!(path.states, [1, 2, 4, 5, 10]);
+        assert!(result.stats.expanded > 0 && result.stats.generated >= result.stats.expanded);
+    }
+
+    #[test]
+    fn bfs_start_is_goal() {
+        let result = bfs(3, |&n| [((), n + 1)], |&n| n == 3);
+        assert_eq!(result.path.unwrap().states, [3]);
+    }
+
+    #[test]
+    fn bfs_unreachable_goal() {
+        let result = bfs(0u8, |&n| [((), n.saturating_add(2))], |&n| n == 7);
+        assert_eq!(result.path, None);
+        assert_eq!(result.stats.discovered, 129);
+    }
+
+    #[test]
+    fn dijkstra_finds_cheapest_path() {
+        let result = dijkstra((10, 0), steps, |&p| p == (10, 19));
+        let path = result.path.unwrap();
+        // Left to the gap at x <= 2, down, then back right.
+        assert_eq!(path.cost, 8 * 2 + 19 + 8);
+        assert_eq!(path.states.first(), Some(&(10, 0)));
+        assert_eq!(path.states.last(), Some(&(10, 19)));
+        let cost: u32 = path.actions.iter().map(|&a| if a == 'R' || a == 'D' { 1 } else { 2 }).sum();
+        assert_eq!(cost, path.cost);
+        assert_eq!(dijkstra((0, 0), steps, |&p| p == (20, 20)).path, None);
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra_with_fewer_expansions() {
+        let goal = (10, 19);
+        let plain = dijkstra((10, 0), steps, |&p| p == goal);
+        let manhattan = |&(x, y): &(i32, i32)| (goal.0 - x).unsigned_abs() + (goal.1 - y).unsigned_abs();
+        let guided = astar((10, 0), steps, |&p| p == goal, manhattan);
+        assert_eq!(plain.path.unwrap().cost, 43);
+        assert_eq!(guided.path.unwrap().cost, 43);
+        assert!(guided.stats.expanded < plain.stats.expanded);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::parse::ParseError;
+use std::any::Any;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2
+        }
+    }
+
+    pub fn from_number(n: u32) -> Option<Part> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(u64),
+    // Returned for parts that have no puzzle, e.g. the second half of Day 12.
+    Unavailable,
+    // The input is well formed but has no answer, with the reason, e.g. a Day
+    // 10 machine whose lights can't be reached.
+    Unsolvable(String)
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Number(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as u64)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Unavailable => write!(f, "-"),
+            Answer::Unsolvable(reason) => write!(f, "no answer: {}", reason)
+        }
+    }
+}
+
+/// A single day's puzzle: parse the input once, then solve each part from the
+/// parsed representation.
+pub trait Solution {
+    const DAY: u32;
+    type Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+    // Parses one of the puzzle's examples. Days whose examples are solved with
+    // different parameters from the real input, e.g. Day 8's number of
+    // connections, override this.
+    fn parse_example(&self, input: &str) -> Result<Self::Input, ParseError> {
+        self.parse(input)
+    }
+    fn part1(&self, input: &Self::Input) -> Answer;
+    fn part2(&self, _input: &Self::Input) -> Answer {
+        Answer::Unavailable
+    }
+}
+
+/// Type-erased view of a [`Solution`] so that days with different input types
+/// can be stored in one table and dispatched by number.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u32;
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+    fn parse_example(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+    fn solve(&self, input: &dyn Any, part: Part) -> Answer;
+
+    fn parse_input(&self, input: &str, example: bool) -> Result<Box<dyn Any>, ParseError> {
+        if example { self.parse_example(input) } else { self.parse(input) }
+    }
+
+    fn run(&self, input: &str, part: Part) -> Result<Answer, ParseError> {
+        Ok(self.solve(self.parse(input)?.as_ref(), part))
+    }
+
+    fn run_example(&self, input: &str, part: Part) -> Result<Answer, ParseError> {
+        Ok(self.solve(self.parse_example(input)?.as_ref(), part))
+    }
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+    S::Input: 'static,
+{
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+    fn parse_example(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(Solution::parse_example(self, input)?))
+    }
+    fn solve(&self, input: &dyn Any, part: Part) -> Answer {
+        let input = input.downcast_ref::<S::Input>().expect("input parsed by a different day");
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input)
+        }
+    }
+}
@@ -0,0 +1,123 @@
+// Union-find over the elements 0..n, with path compression and union by size.
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    num_components: usize
+}
+
+impl DisjointSet {
+    pub fn new(n: usize) -> Self {
+        DisjointSet { parent: (0..n).collect(), size: vec![1; n], num_components: n }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn num_components(&self) -> usize {
+        self.num_components
+    }
+
+    // The representative of the component containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut x = x;
+        while self.parent[x] != root {
+            x = std::mem::replace(&mut self.parent[x], root);
+        }
+        root
+    }
+
+    // Merges the components containing `a` and `b`, returning false if they
+    // were already the same component.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (large, small) = if self.size[a] >= self.size[b] { (a, b) } else { (b, a) };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+        self.num_components -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn component_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    // The size of every component, in order of their representatives.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        (0..self.len()).filter(|&x| self.parent[x] == x).map(|root| self.size[root]).collect()
+    }
+
+    // The elements in the same component as `x`, in increasing order.
+    pub fn members(&mut self, x: usize) -> Vec<usize> {
+        let root = self.find(x);
+        (0..self.len()).filter(|&y| self.find(y) == root).collect()
+    }
+
+    // Every component as a list of its elements, ordered by smallest element.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        for x in 0..self.len() {
+            let root = self.find(x);
+            if index[root] == usize::MAX {
+                index[root] = components.len();
+                components.push(Vec::new());
+            }
+            components[index[root]].push(x);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unions_merge_components() {
+        let mut set = DisjointSet::new(6);
+        assert_eq!(set.num_components(), 6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+        assert_eq!(set.num_components(), 3);
+        assert!(set.same(0, 3));
+        assert!(!set.same(0, 4));
+        assert_eq!(set.component_size(2), 4);
+        let mut sizes = set.component_sizes();
+        sizes.sort();
+        assert_eq!(sizes, [1, 1, 4]);
+        assert_eq!(set.members(3), [0, 1, 2, 3]);
+        assert_eq!(set.components(), [vec![0, 1, 2, 3], vec![4], vec![5]]);
+    }
+
+    #[test]
+    fn long_chains_are_compressed() {
+        let mut set = DisjointSet::new(1000);
+        for i in 1..1000 {
+            set.union(i - 1, i);
+        }
+        assert_eq!(set.num_components(), 1);
+        let root = set.find(999);
+        assert_eq!(set.parent[999], root);
+        assert_eq!(set.component_size(0), 1000);
+    }
+}
@@ -0,0 +1,356 @@
+use std::fmt;
+use std::ops::{Add, Mul, RangeInclusive, Sub};
+
+// The numeric types points can be made of.
+pub trait Coordinate: Copy + PartialOrd + Add<Output=Self> + Sub<Output=Self> + Mul<Output=Self> + Default {}
+
+impl<T: Copy + PartialOrd + Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Default> Coordinate for T {}
+
+// |a - b| without going below zero for unsigned types.
+fn abs_diff<T: Coordinate>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+fn min<T: Coordinate>(a: T, b: T) -> T {
+    if b < a { b } else { a }
+}
+
+fn max<T: Coordinate>(a: T, b: T) -> T {
+    if b > a { b } else { a }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T
+}
+
+impl<T> Point2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Point2<U> {
+        Point2 { x: f(self.x), y: f(self.y) }
+    }
+}
+
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Point3<U> {
+        Point3 { x: f(self.x), y: f(self.y), z: f(self.z) }
+    }
+}
+
+impl<T: Coordinate> Point2<T> {
+    pub fn manhattan_distance(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    pub fn squared_distance(self, other: Self) -> T {
+        let (dx, dy) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y));
+        dx * dx + dy * dy
+    }
+
+    pub fn component_min(self, other: Self) -> Self {
+        Point2 { x: min(self.x, other.x), y: min(self.y, other.y) }
+    }
+
+    pub fn component_max(self, other: Self) -> Self {
+        Point2 { x: max(self.x, other.x), y: max(self.y, other.y) }
+    }
+}
+
+impl<T: Coordinate> Point3<T> {
+    pub fn manhattan_distance(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    pub fn squared_distance(self, other: Self) -> T {
+        let (dx, dy, dz) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y), abs_diff(self.z, other.z));
+        dx * dx + dy * dy + dz * dz
+    }
+
+    pub fn component_min(self, other: Self) -> Self {
+        Point3 { x: min(self.x, other.x), y: min(self.y, other.y), z: min(self.z, other.z) }
+    }
+
+    pub fn component_max(self, other: Self) -> Self {
+        Point3 { x: max(self.x, other.x), y: max(self.y, other.y), z: max(self.z, other.z) }
+    }
+}
+
+impl<T: Add<Output=T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Point2 { x: self.x + rhs.x, y: self.y + rhs.y }
+    }
+}
+
+impl<T: Sub<Output=T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Point2 { x: self.x - rhs.x, y: self.y - rhs.y }
+    }
+}
+
+impl<T: Copy + Mul<Output=T>> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Point2 { x: self.x * rhs, y: self.y * rhs }
+    }
+}
+
+impl<T: Add<Output=T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Point3 { x: self.x + rhs.x, y: self.y + rhs.y, z: self.z + rhs.z }
+    }
+}
+
+impl<T: Sub<Output=T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Point3 { x: self.x - rhs.x, y: self.y - rhs.y, z: self.z - rhs.z }
+    }
+}
+
+impl<T: Copy + Mul<Output=T>> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Point3 { x: self.x * rhs, y: self.y * rhs, z: self.z * rhs }
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+// Points print the way puzzle inputs write them, e.g. "7,3".
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+// The smallest box containing a set of points, with both corners included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox<P> {
+    pub min: P,
+    pub max: P
+}
+
+impl<T: Coordinate> BoundingBox<Point2<T>> {
+    // None if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item=Point2<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(BoundingBox { min: first, max: first }, |b, p| {
+            BoundingBox { min: b.min.component_min(p), max: b.max.component_max(p) }
+        }))
+    }
+
+    pub fn contains(&self, point: Point2<T>) -> bool {
+        self.min.x <= point.x && point.x <= self.max.x && self.min.y <= point.y && point.y <= self.max.y
+    }
+}
+
+impl<T: Coordinate> BoundingBox<Point3<T>> {
+    pub fn from_points(points: impl IntoIterator<Item=Point3<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(BoundingBox { min: first, max: first }, |b, p| {
+            BoundingBox { min: b.min.component_min(p), max: b.max.component_max(p) }
+        }))
+    }
+
+    pub fn contains(&self, point: Point3<T>) -> bool {
+        self.min.x <= point.x && point.x <= self.max.x &&
+        self.min.y <= point.y && point.y <= self.max.y &&
+        self.min.z <= point.z && point.z <= self.max.z
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolygonError {
+    TooFewVertices(usize),
+    // The vertex at this index and the one after it (wrapping round) don't
+    // share exactly one axis.
+    NotRectilinear(usize)
+}
+
+impl fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolygonError::TooFewVertices(n) => write!(f, "a polygon needs at least 4 vertices but got {}", n),
+            PolygonError::NotRectilinear(i) => write!(f, "vertices {} and {} are not joined by a horizontal or vertical edge", i, i + 1)
+        }
+    }
+}
+
+// A closed polygon whose edges are all horizontal or vertical, given by its
+// vertices in order. The last vertex joins back to the first.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RectilinearPolygon<T> {
+    vertices: Vec<Point2<T>>
+}
+
+impl<T: Coordinate + Into<i128>> RectilinearPolygon<T> {
+    pub fn new(vertices: Vec<Point2<T>>) -> Result<Self, PolygonError> {
+        if vertices.len() < 4 {
+            return Err(PolygonError::TooFewVertices(vertices.len()));
+        }
+        let polygon = RectilinearPolygon { vertices };
+        if let Some(i) = polygon.edges().position(|(a, b)| (a.x == b.x) == (a.y == b.y)) {
+            return Err(PolygonError::NotRectilinear(i));
+        }
+        Ok(polygon)
+    }
+
+    pub fn vertices(&self) -> &[Point2<T>] {
+        &self.vertices
+    }
+
+    // Each pair of consecutive vertices, ending with the edge from the last
+    // vertex back to the first.
+    pub fn edges(&self) -> impl Iterator<Item=(Point2<T>, Point2<T>)> + '_ {
+        self.vertices.iter().zip(self.vertices.iter().cycle().skip(1)).map(|(&a, &b)| (a, b))
+    }
+
+    // Horizontal edges as their y and the x values they span.
+    pub fn horizontal_edges(&self) -> impl Iterator<Item=(T, RangeInclusive<T>)> + '_ {
+        self.edges().filter(|(a, b)| a.y == b.y).map(|(a, b)| (a.y, min(a.x, b.x)..=max(a.x, b.x)))
+    }
+
+    // Vertical edges as their x and the y values they span.
+    pub fn vertical_edges(&self) -> impl Iterator<Item=(T, RangeInclusive<T>)> + '_ {
+        self.edges().filter(|(a, b)| a.x == b.x).map(|(a, b)| (a.x, min(a.y, b.y)..=max(a.y, b.y)))
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox<Point2<T>> {
+        BoundingBox::<Point2<T>>::from_points(self.vertices.iter().copied()).unwrap()
+    }
+
+    // The area enclosed by the edges, from the shoelace formula.
+    pub fn area(&self) -> u128 {
+        let twice_area: i128 = self.edges().map(|(a, b)| a.x.into() * b.y.into() - b.x.into() * a.y.into()).sum();
+        twice_area.unsigned_abs() / 2
+    }
+
+    pub fn perimeter(&self) -> u128 {
+        self.edges().map(|(a, b)| Into::<i128>::into(a.manhattan_distance(b)) as u128).sum()
+    }
+
+    // The number of integer points on or inside the boundary, which by Pick's
+    // theorem is the area plus half the boundary points plus one.
+    pub fn num_points(&self) -> u128 {
+        self.area() + self.perimeter() / 2 + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The Day 9 example.
+    fn example() -> RectilinearPolygon<u32> {
+        let vertices = [(7, 1), (11, 1), (11, 7), (9, 7), (9, 5), (2, 5), (2, 3), (7, 3)];
+        RectilinearPolygon::new(vertices.into_iter().map(Point2::from).collect()).unwrap()
+    }
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (Point2::new(1, 5), Point2::new(4, -2));
+        assert_eq!(a + b, Point2::new(5, 3));
+        assert_eq!(a - b, Point2::new(-3, 7));
+        assert_eq!(a * 3, Point2::new(3, 15));
+        assert_eq!(Point3::new(1, 2, 3) + Point3::new(1, 1, 1) * 2, Point3::new(3, 4, 5));
+        assert_eq!(Point2::new(1u8, 2).map(u32::from), Point2::new(1u32, 2));
+        assert_eq!(Point3::new(162, 817, 812).to_string(), "162,817,812");
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point2::new(9u32, 2), Point2::new(3, 10));
+        assert_eq!(a.manhattan_distance(b), 14);
+        assert_eq!(b.manhattan_distance(a), 14);
+        assert_eq!(a.squared_distance(b), 100);
+        let (a, b) = (Point3::new(1u64, 7, 2), Point3::new(4, 3, 2));
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(a.squared_distance(b), 25);
+        assert_eq!(Point3::new(0.0, 3.0, 0.0).squared_distance(Point3::new(4.0, 0.0, 0.0)), 25.0);
+    }
+
+    #[test]
+    fn bounding_boxes() {
+        let points = [Point2::new(3, 8), Point2::new(-1, 4), Point2::new(5, 6)];
+        let bounds = BoundingBox::<Point2<i32>>::from_points(points).unwrap();
+        assert_eq!(bounds, BoundingBox { min: Point2::new(-1, 4), max: Point2::new(5, 8) });
+        assert!(points.iter().all(|&p| bounds.contains(p)));
+        assert!(!bounds.contains(Point2::new(5, 9)));
+        assert_eq!(BoundingBox::<Point2<i32>>::from_points([]), None);
+        let bounds = BoundingBox::<Point3<u8>>::from_points([Point3::new(1, 9, 4), Point3::new(6, 2, 4)]).unwrap();
+        assert_eq!(bounds, BoundingBox { min: Point3::new(1, 2, 4), max: Point3::new(6, 9, 4) });
+        assert!(bounds.contains(Point3::new(3, 3, 4)) && !bounds.contains(Point3::new(3, 3, 5)));
+    }
+
+    #[test]
+    fn polygon_edges() {
+        let polygon = example();
+        let horizontal: Vec<_> = polygon.horizontal_edges().collect();
+        assert_eq!(horizontal, [(1, 7..=11), (7, 9..=11), (5, 2..=9), (3, 2..=7)]);
+        let vertical: Vec<_> = polygon.vertical_edges().collect();
+        assert_eq!(vertical, [(11, 1..=7), (9, 5..=7), (2, 3..=5), (7, 1..=3)]);
+        assert_eq!(polygon.bounding_box(), BoundingBox { min: Point2::new(2, 1), max: Point2::new(11, 7) });
+    }
+
+    #[test]
+    fn polygon_measurements() {
+        let polygon = example();
+        assert_eq!(polygon.perimeter(), 30);
+        assert_eq!(polygon.area(), 4 * 2 + 9 * 2 + 2 * 2);
+        // The tiles drawn in the Day 9 puzzle.
+        assert_eq!(polygon.num_points(), 46);
+        let square = RectilinearPolygon::new(vec![Point2::new(0i64, 0), Point2::new(0, 3), Point2::new(3, 3), Point2::new(3, 0)]);
+        assert_eq!(square.unwrap().area(), 9);
+    }
+
+    #[test]
+    fn polygon_validation() {
+        let points = |coords: &[(u32, u32)]| coords.iter().copied().map(Point2::from).collect::<Vec<_>>();
+        assert_eq!(RectilinearPolygon::new(points(&[(0, 0), (0, 1), (1, 1)])), Err(PolygonError::TooFewVertices(3)));
+        assert_eq!(RectilinearPolygon::new(points(&[(0, 0), (0, 2), (2, 2), (1, 0)])), Err(PolygonError::NotRectilinear(2)));
+        assert_eq!(RectilinearPolygon::new(points(&[(0, 0), (0, 2), (2, 2), (2, 1)])), Err(PolygonError::NotRectilinear(3)));
+        assert_eq!(RectilinearPolygon::new(points(&[(0, 0), (0, 2), (0, 2), (2, 2), (2, 0)])), Err(PolygonError::NotRectilinear(1)));
+    }
+}
@@ -0,0 +1,255 @@
+use crate::parse::{self, Line, ParseError};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize
+}
+
+impl Point {
+    pub fn new(row: usize, col: usize) -> Self {
+        Point { row, col }
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((row, col): (usize, usize)) -> Self {
+        Point { row, col }
+    }
+}
+
+const NEIGHBOURS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const NEIGHBOURS8: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+// A rectangular grid stored row by row in a single Vec. Cells are addressed by
+// (row, col) tuples or Points, with row 0 at the top.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>
+}
+
+impl<T> Grid<T> {
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), rows * cols, "a {}x{} grid needs {} cells", rows, cols, rows * cols);
+        Grid { rows, cols, cells }
+    }
+
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..rows * cols).map(|i| f(Point::new(i / cols, i % cols))).collect();
+        Grid { rows, cols, cells }
+    }
+
+    // Parses a character map, one row per line, mapping each character with
+    // `cell`. Characters it rejects are reported as not being `expected`, and
+    // every row must be as wide as the first.
+    pub fn parse(input: &str, cell: impl FnMut(char) -> Option<T>, expected: &str) -> Result<Self, ParseError> {
+        let lines: Vec<Line> = parse::lines(input).collect();
+        if lines.is_empty() {
+            return Err(parse::end_of_input(input, "a grid"));
+        }
+        Grid::parse_lines(&lines, cell, expected)
+    }
+
+    // As parse, for a character map that is part of a larger input, such as
+    // one of its sections. `lines` must not be empty.
+    pub fn parse_lines(lines: &[Line], mut cell: impl FnMut(char) -> Option<T>, expected: &str) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut cols = None;
+        for line in lines {
+            let mut width = 0;
+            for (i, c) in line.text.char_indices() {
+                let Some(value) = cell(c) else { return Err(line.error(line.char_at(i), expected)); };
+                cells.push(value);
+                width += 1;
+            }
+            let cols = *cols.get_or_insert(width);
+            if width != cols {
+                return Err(ParseError::new(line.number, width.min(cols) + 1, format!("a row of width {}", cols), format!("width {}", width)));
+            }
+        }
+        let cols = cols.expect("a grid needs at least one line");
+        Ok(Grid { rows: lines.len(), cols, cells })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, point: impl Into<Point>) -> bool {
+        let Point { row, col } = point.into();
+        row < self.rows && col < self.cols
+    }
+
+    pub fn get(&self, point: impl Into<Point>) -> Option<&T> {
+        let point = point.into();
+        self.contains(point).then(|| &self.cells[point.row * self.cols + point.col])
+    }
+
+    pub fn get_mut(&mut self, point: impl Into<Point>) -> Option<&mut T> {
+        let point = point.into();
+        self.contains(point).then(|| &mut self.cells[point.row * self.cols + point.col])
+    }
+
+    // Every point in row-major order.
+    pub fn points(&self) -> impl Iterator<Item=Point> + use<T> {
+        let cols = self.cols;
+        (0..self.rows * cols).map(move |i| Point::new(i / cols, i % cols))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item=&T> {
+        assert!(col < self.cols, "column {} out of bounds", col);
+        self.cells.iter().skip(col).step_by(self.cols)
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item=&[T]> {
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    pub fn iter_columns(&self) -> impl Iterator<Item=impl Iterator<Item=&T>> {
+        (0..self.cols).map(|col| self.column(col))
+    }
+
+    fn offset(&self, point: Point, (dr, dc): (isize, isize)) -> Option<Point> {
+        let row = point.row.checked_add_signed(dr)?;
+        let col = point.col.checked_add_signed(dc)?;
+        self.contains((row, col)).then_some(Point::new(row, col))
+    }
+
+    // The orthogonal neighbours of `point` that lie inside the grid.
+    pub fn neighbours4(&self, point: impl Into<Point>) -> impl Iterator<Item=Point> + '_ {
+        let point = point.into();
+        NEIGHBOURS4.into_iter().filter_map(move |offset| self.offset(point, offset))
+    }
+
+    // The orthogonal and diagonal neighbours of `point` that lie inside the grid.
+    pub fn neighbours8(&self, point: impl Into<Point>) -> impl Iterator<Item=Point> + '_ {
+        let point = point.into();
+        NEIGHBOURS8.into_iter().filter_map(move |offset| self.offset(point, offset))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { rows: self.rows, cols: self.cols, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(rows: usize, cols: usize, value: T) -> Self {
+        Grid { rows, cols, cells: vec![value; rows * cols] }
+    }
+
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.cols, self.rows, |Point { row, col }| self[(col, row)].clone())
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        Grid::from_fn(self.cols, self.rows, |Point { row, col }| self[(self.rows - 1 - col, row)].clone())
+    }
+
+    pub fn rotate_anticlockwise(&self) -> Self {
+        Grid::from_fn(self.cols, self.rows, |Point { row, col }| self[(col, self.cols - 1 - row)].clone())
+    }
+}
+
+impl<T, P: Into<Point>> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: P) -> &T {
+        let point = point.into();
+        assert!(self.contains(point), "{:?} out of bounds for a {}x{} grid", point, self.rows, self.cols);
+        &self.cells[point.row * self.cols + point.col]
+    }
+}
+
+impl<T, P: Into<Point>> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, point: P) -> &mut T {
+        let point = point.into();
+        assert!(self.contains(point), "{:?} out of bounds for a {}x{} grid", point, self.rows, self.cols);
+        &mut self.cells[point.row * self.cols + point.col]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Grid<char> {
+        Grid::parse(text, Some, "any character").unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let g = grid("ab\ncd\nef\n");
+        assert_eq!((g.rows(), g.cols()), (3, 2));
+        assert_eq!(g[(2, 0)], 'e');
+        assert_eq!(g[Point::new(0, 1)], 'b');
+        assert_eq!(g.to_string(), "ab\ncd\nef\n");
+    }
+
+    #[test]
+    fn parse_errors_are_located() {
+        let digits = |c: char| c.to_digit(10);
+        let err = Grid::parse("12\n3x\n", digits, "a digit").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 2, "'x'"));
+        let err = Grid::parse("12\n345\n", digits, "a digit").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (2, 3, "a row of width 2"));
+        assert!(Grid::parse("", digits, "a digit").is_err());
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let g = grid("abc\ndef\n");
+        assert_eq!(g.row(1), ['d', 'e', 'f']);
+        assert_eq!(g.column(1).collect::<String>(), "be");
+        assert_eq!(g.iter_columns().map(|col| col.collect::<String>()).collect::<Vec<_>>(), ["ad", "be", "cf"]);
+        assert_eq!(g.get((2, 0)), None);
+        assert_eq!(g.get((0, 3)), None);
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let g = grid("abc\ndef\nghi\n");
+        let at = |points: Vec<Point>| points.into_iter().map(|p| g[p]).collect::<String>();
+        assert_eq!(at(g.neighbours4((0, 0)).collect()), "bd");
+        assert_eq!(at(g.neighbours8((0, 0)).collect()), "bde");
+        assert_eq!(at(g.neighbours4((1, 1)).collect()), "bdfh");
+        assert_eq!(at(g.neighbours8((1, 1)).collect()), "abcdfghi");
+        assert_eq!(at(g.neighbours8((2, 2)).collect()), "efh");
+    }
+
+    #[test]
+    fn transform() {
+        let g = grid("abc\ndef\n");
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(g.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(g.rotate_anticlockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(g.rotate_clockwise().rotate_anticlockwise(), g);
+    }
+}
@@ -0,0 +1,239 @@
+use std::ops::{Add, Range, RangeInclusive, Sub};
+
+// The integer types an IntervalSet can hold.
+pub trait Bound: Copy + Ord + Add<Output=Self> + Sub<Output=Self> + From<u8> {
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_bound {
+    ($($t:ty),*) => {$(
+        impl Bound for $t {
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+        }
+    )*};
+}
+
+impl_bound!(u8, u16, u32, u64, u128, usize, i16, i32, i64, i128, isize);
+
+// A set of integers stored as sorted, disjoint half-open ranges. Ranges are
+// merged on insert when they overlap or touch, so 3..6 and 6..9 become 3..9.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_half_open(ranges: impl IntoIterator<Item=Range<T>>) -> Self {
+        let mut set = Self::new();
+        ranges.into_iter().for_each(|range| set.insert(range));
+        set
+    }
+
+    // Inclusive ranges such as 3-5 in puzzle inputs, or None if any of them
+    // ends at T's maximum.
+    pub fn from_inclusive(ranges: impl IntoIterator<Item=RangeInclusive<T>>) -> Option<Self> {
+        let mut set = Self::new();
+        for range in ranges {
+            set.insert_inclusive(range)?;
+        }
+        Some(set)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        // Ranges from `first` to `last` overlap or touch the new one.
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let mut merged = range;
+        if first < last {
+            merged.start = merged.start.min(self.ranges[first].start);
+            merged.end = merged.end.max(self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    // Returns None, leaving the set unchanged, if the range ends at T's
+    // maximum since the set can't hold the half-open range's end.
+    pub fn insert_inclusive(&mut self, range: RangeInclusive<T>) -> Option<()> {
+        let (low, high) = range.into_inner();
+        self.insert(low..high.checked_add(T::from(1))?);
+        Some(())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn num_ranges(&self) -> usize {
+        self.ranges.len()
+    }
+
+    // The number of values in the set.
+    pub fn total_length(&self) -> T {
+        self.ranges.iter().fold(T::from(0), |total, r| total + (r.end - r.start))
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Range<T>> {
+        self.ranges.iter()
+    }
+
+    // The ranges as (low, high) pairs with both ends included.
+    pub fn iter_inclusive(&self) -> impl Iterator<Item=(T, T)> + '_ {
+        self.ranges.iter().map(|r| (r.start, r.end - T::from(1)))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        other.iter().for_each(|range| set.insert(range.clone()));
+        set
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if a.end < b.end { i += 1 } else { j += 1 }
+        }
+        IntervalSet { ranges }
+    }
+
+    // The values in `bounds` that are not in the set.
+    pub fn complement(&self, bounds: Range<T>) -> Self {
+        let mut ranges = Vec::new();
+        let mut start = bounds.start;
+        for r in &self.ranges {
+            if r.start > start {
+                ranges.push(start..r.start.min(bounds.end));
+            }
+            start = start.max(r.end);
+            if start >= bounds.end {
+                break;
+            }
+        }
+        if start < bounds.end {
+            ranges.push(start..bounds.end);
+        }
+        ranges.retain(|r| !r.is_empty());
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let (Some(first), Some(last)) = (self.ranges.first(), self.ranges.last()) else { return Self::new(); };
+        self.intersection(&other.complement(first.start..last.end))
+    }
+}
+
+impl<T: Bound> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item=Range<T>>>(ranges: I) -> Self {
+        Self::from_half_open(ranges)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Range<T>;
+    type IntoIter = std::slice::Iter<'a, Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Half-open ranges as (start, end) pairs.
+    fn ranges(set: &IntervalSet<u32>) -> Vec<(u32, u32)> {
+        set.iter().map(|r| (r.start, r.end)).collect()
+    }
+
+    #[test]
+    fn insert_normalises() {
+        let set = IntervalSet::from_half_open([10..15, 12..19, 16..21, 1..2, 30..30]);
+        assert_eq!(ranges(&set), [(1, 2), (10, 21)]);
+        let set = IntervalSet::from_half_open([5..8, 0..3, 3..5, 20..25, 9..10]);
+        assert_eq!(ranges(&set), [(0, 8), (9, 10), (20, 25)]);
+        let set = IntervalSet::from_half_open([0..3, 5..7, 9..10, 2..9]);
+        assert_eq!(ranges(&set), [(0, 10)]);
+    }
+
+    #[test]
+    fn inclusive_ranges_merge_when_adjacent() {
+        let set = IntervalSet::from_inclusive([3..=5, 6..=8, 10..=10]).unwrap();
+        assert_eq!(set.iter_inclusive().collect::<Vec<_>>(), [(3, 8), (10, 10)]);
+        assert_eq!(set.total_length(), 7);
+    }
+
+    #[test]
+    fn inclusive_ranges_up_to_the_maximum() {
+        let mut set = IntervalSet::from_inclusive([0..=u8::MAX - 1]).unwrap();
+        assert_eq!(set.total_length(), u8::MAX);
+        assert_eq!(set.insert_inclusive(10..=u8::MAX), None);
+        assert_eq!(set.iter_inclusive().collect::<Vec<_>>(), [(0, u8::MAX - 1)]);
+        assert_eq!(IntervalSet::from_inclusive([1..=2u64, 5..=u64::MAX]), None);
+    }
+
+    #[test]
+    fn membership() {
+        let set = IntervalSet::from_inclusive([3..=5u64, 10..=14]).unwrap();
+        let members: Vec<u64> = (0..20).filter(|&x| set.contains(x)).collect();
+        assert_eq!(members, [3, 4, 5, 10, 11, 12, 13, 14]);
+        assert!(!IntervalSet::<u64>::new().contains(0));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = IntervalSet::from_half_open([0..10, 20..30]);
+        let b = IntervalSet::from_half_open([5..25, 40..50]);
+        assert_eq!(ranges(&a.union(&b)), [(0, 30), (40, 50)]);
+        assert_eq!(ranges(&a.intersection(&b)), [(5, 10), (20, 25)]);
+        assert_eq!(ranges(&a.difference(&b)), [(0, 5), (25, 30)]);
+        assert_eq!(ranges(&b.difference(&a)), [(10, 20), (40, 50)]);
+        assert_eq!(ranges(&a.complement(5..35)), [(10, 20), (30, 35)]);
+        assert_eq!(ranges(&a.complement(0..30)), [(10, 20)]);
+        assert_eq!(ranges(&IntervalSet::new().complement(1..4)), [(1, 4)]);
+    }
+
+    #[test]
+    fn operations_agree_with_brute_force() {
+        let mut rng = crate::generator::Rng::new(1);
+        let mut random_set = || IntervalSet::from_half_open((0..5).map(|_| {
+            let start = rng.below(40) as u32;
+            start..start + rng.below(8) as u32
+        }));
+        for _ in 0..200 {
+            let (a, b) = (random_set(), random_set());
+            for x in 0..50 {
+                assert_eq!(a.union(&b).contains(x), a.contains(x) || b.contains(x));
+                assert_eq!(a.intersection(&b).contains(x), a.contains(x) && b.contains(x));
+                assert_eq!(a.difference(&b).contains(x), a.contains(x) && !b.contains(x));
+                assert_eq!(a.complement(10..40).contains(x), (10..40).contains(&x) && !a.contains(x));
+            }
+            assert_eq!(a.total_length() as usize, (0..50).filter(|&x| a.contains(x)).count());
+        }
+    }
+}
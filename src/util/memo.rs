@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+
+// A cache for recursive functions. The function is given a Recurse handle to
+// look up the values it depends on, which are computed on first use and
+// cached. Asking for a value while it is still being computed means the
+// recursion has a cycle, which is reported as an error rather than
+// overflowing the stack.
+//
+//     let mut memo = Memo::new();
+//     let fib = memo.get(90, |memo, &n| {
+//         Ok(if n < 2 { n } else { memo.get(n - 1)? + memo.get(n - 2)? })
+//     });
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    // None while the value is being computed.
+    values: HashMap<K, Option<V>>,
+    hits: usize,
+    misses: usize
+}
+
+// The key whose value ended up depending on itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<K> {
+    pub key: K
+}
+
+impl<K: fmt::Debug> fmt::Display for CycleError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the value for {:?} depends on itself", self.key)
+    }
+}
+
+impl<K: fmt::Debug> Error for CycleError<K> {}
+
+type Function<'a, K, V> = dyn Fn(&mut Recurse<K, V>, &K) -> Result<V, CycleError<K>> + 'a;
+
+// Passed to the memoised function for its recursive calls.
+pub struct Recurse<'a, K, V> {
+    memo: &'a mut Memo<K, V>,
+    f: &'a Function<'a, K, V>
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Recurse<'_, K, V> {
+    pub fn get(&mut self, key: K) -> Result<V, CycleError<K>> {
+        self.memo.get_with(key, self.f)
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo { values: HashMap::new(), hits: 0, misses: 0 }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The value of `f` for `key`, computing it and anything it depends on
+    // unless already cached. Values cached by earlier calls are reused, so the
+    // same `f` should be passed every time.
+    pub fn get(&mut self, key: K, f: impl Fn(&mut Recurse<K, V>, &K) -> Result<V, CycleError<K>>) -> Result<V, CycleError<K>> {
+        self.get_with(key, &f)
+    }
+
+    fn get_with(&mut self, key: K, f: &Function<K, V>) -> Result<V, CycleError<K>> {
+        match self.values.get(&key) {
+            Some(Some(value)) => {
+                self.hits += 1;
+                return Ok(value.clone());
+            }
+            Some(None) => return Err(CycleError { key }),
+            None => {}
+        }
+        self.misses += 1;
+        self.values.insert(key.clone(), None);
+        let result = f(&mut Recurse { memo: self, f }, &key);
+        match &result {
+            Ok(value) => { self.values.insert(key, Some(value.clone())); }
+            // Forget the unfinished value so the memo stays usable.
+            Err(_) => { self.values.remove(&key); }
+        }
+        result
+    }
+
+    // The cached value for `key`, without computing it.
+    pub fn cached(&self, key: &K) -> Option<&V> {
+        self.values.get(key).and_then(|value| value.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    // Lookups answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    // Lookups that had to compute their value.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    // The fraction of lookups answered from the cache, or 0 if there were none.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 { 0.0 } else { self.hits as f64 / lookups as f64 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> Result<u64, CycleError<u64>> {
+        memo.get(n, |memo, &n| Ok(if n < 2 { n } else { memo.get(n - 1)? + memo.get(n - 2)? }))
+    }
+
+    #[test]
+    fn caches_recursive_calls() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(&mut memo, 90), Ok(2880067194370816120));
+        // Each value is computed once and then looked up once more by the
+        // value two above it.
+        assert_eq!((memo.misses(), memo.hits()), (91, 88));
+        assert_eq!(memo.len(), 91);
+        assert_eq!(memo.cached(&10), Some(&55));
+        assert_eq!(fibonacci(&mut memo, 50), Ok(12586269025));
+        assert_eq!((memo.misses(), memo.hits()), (91, 89));
+        assert!((memo.hit_rate() - 89.0 / 180.0).abs() < 1e-12);
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.hit_rate(), 0.0);
+    }
+
+    #[test]
+    fn closures_can_capture() {
+        let costs = [3, 1, 4, 1, 5, 9, 2, 6];
+        // The cheapest way to reach the end, stepping one or two places.
+        let cheapest = |memo: &mut Recurse<usize, u32>, &i: &usize| {
+            if i >= costs.len() {
+                return Ok(0);
+            }
+            Ok(costs[i] + memo.get(i + 1)?.min(memo.get(i + 2)?))
+        };
+        let mut memo = Memo::new();
+        assert_eq!(memo.get(0, cheapest).unwrap().min(memo.get(1, cheapest).unwrap()), 1 + 1 + 5 + 2);
+    }
+
+    #[test]
+    fn cycles_are_errors() {
+        // 0 -> 1 -> 2 -> 0, with 3 off to the side.
+        let next = [1, 2, 0, 3];
+        let mut memo = Memo::<usize, u32>::new();
+        let depth = |memo: &mut Recurse<usize, u32>, &i: &usize| {
+            if next[i] == i { Ok(0) } else { Ok(memo.get(next[i])? + 1) }
+        };
+        assert_eq!(memo.get(1, depth), Err(CycleError { key: 1 }));
+        assert!(memo.is_empty());
+        assert_eq!(memo.get(3, depth), Ok(0));
+        assert_eq!(CycleError { key: 1 }.to_string(), "the value for 1 depends on itself");
+    }
+}
@@ -0,0 +1,35 @@
+mod disjoint_set;
+mod geometry;
+mod grid;
+mod interval_set;
+mod memo;
+pub mod number;
+
+pub use disjoint_set::DisjointSet;
+pub use geometry::{BoundingBox, Coordinate, Point2, Point3, PolygonError, RectilinearPolygon};
+pub use grid::{Grid, Point};
+pub use interval_set::IntervalSet;
+pub use memo::{CycleError, Memo, Recurse};
+
+use std::time::Duration;
+
+pub fn cartestian_product<I, J>(iter1: I, iter2: J) -> impl Iterator<Item=(I::Item, J::Item)>
+where
+    I: Iterator,
+    J: Iterator + Clone,
+    I::Item: Copy,
+    J::Item: Copy,
+{
+    iter1.flat_map(move |i| iter2.clone().map(move |j| (i, j)))
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{:.2}s", secs)
+    } else if secs >= 1e-3 {
+        format!("{:.2}ms", secs * 1e3)
+    } else {
+        format!("{:.1}µs", secs * 1e6)
+    }
+}
@@ -0,0 +1,273 @@
+use std::ops::{Add, Rem};
+
+// Digit manipulation and number theory on u64. Nothing here allocates, so it
+// is cheap to call in the inner loops of the puzzles.
+
+const fn pow10_table() -> [u64; 20] {
+    let mut table = [1; 20];
+    let mut i = 1;
+    while i < 20 {
+        table[i] = table[i - 1] * 10;
+        i += 1;
+    }
+    table
+}
+
+// Every power of ten that fits in a u64.
+pub const POW10: [u64; 20] = pow10_table();
+
+pub fn pow10(exponent: u32) -> u64 {
+    POW10[exponent as usize]
+}
+
+// The number of digits needed to write `n` in `base`. Zero has one digit.
+pub fn num_digits(n: u64, base: u32) -> u32 {
+    if base == 10 {
+        return POW10.partition_point(|&power| power <= n).max(1) as u32;
+    }
+    let mut count = 1;
+    let mut n = n / base as u64;
+    while n > 0 {
+        count += 1;
+        n /= base as u64;
+    }
+    count
+}
+
+// The digits of a number, most significant first. Iterating from the back
+// gives them least significant first.
+#[derive(Debug, Clone)]
+pub struct Digits {
+    n: u64,
+    base: u64,
+    // The place value of the next digit from the front.
+    place: u64,
+    len: u32
+}
+
+pub fn digits(n: u64, base: u32) -> Digits {
+    let len = num_digits(n, base);
+    Digits { n, base: base as u64, place: (base as u64).pow(len - 1), len }
+}
+
+impl Iterator for Digits {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+        let digit = self.n / self.place;
+        self.n %= self.place;
+        self.place /= self.base;
+        self.len -= 1;
+        Some(digit as u32)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len as usize, Some(self.len as usize))
+    }
+}
+
+impl DoubleEndedIterator for Digits {
+    fn next_back(&mut self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+        let digit = self.n % self.base;
+        self.n /= self.base;
+        self.place /= self.base;
+        self.len -= 1;
+        Some(digit as u32)
+    }
+}
+
+impl ExactSizeIterator for Digits {}
+
+// The number whose digits in `base` are `digits`, most significant first.
+pub fn from_digits(digits: impl IntoIterator<Item=u32>, base: u32) -> u64 {
+    digits.into_iter().fold(0, |n, digit| n * base as u64 + digit as u64)
+}
+
+// The decimal number made of `times` copies of the `block_len` digit block
+// 0..01, e.g. repunit(2, 3) is 10101. Multiplying it by any block of that
+// length repeats the block.
+pub fn repunit(block_len: u32, times: u32) -> u64 {
+    (0..times).map(|i| pow10(block_len * i)).sum()
+}
+
+// Whether the decimal digits of `n` are some block repeated `times` times,
+// like 123123 for twice.
+pub fn is_repeated(n: u64, times: u32) -> bool {
+    let len = num_digits(n, 10);
+    len.is_multiple_of(times) && n.is_multiple_of(repunit(len / times, times))
+}
+
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 { 0 } else { a / gcd(a, b) * b }
+}
+
+// The remainder of a / b with the sign of b, so -1 modulo 100 is 99.
+pub fn modulo<T: Copy + Add<Output=T> + Rem<Output=T>>(a: T, b: T) -> T {
+    ((a % b) + b) % b
+}
+
+// base ^ exponent % m without overflowing.
+pub fn mod_pow(base: u64, exponent: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    let mut base = (base % m) as u128;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = (result as u128 * base % m as u128) as u64;
+        }
+        base = base * base % m as u128;
+        exponent >>= 1;
+    }
+    result
+}
+
+// The x with a * x = 1 modulo m, if a and m are coprime.
+pub fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+    // Extended Euclid, tracking only the coefficient of a.
+    let (mut r0, mut r1) = (m as i128, (a % m) as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    (r0 == 1).then(|| modulo(t0, m as i128) as u64)
+}
+
+// The divisors of a number in increasing order.
+#[derive(Debug, Clone)]
+pub struct Divisors {
+    n: u64,
+    i: u64,
+    // Whether the candidates below the square root are done and `i` now
+    // counts back down, giving the matching divisor n / i.
+    descending: bool
+}
+
+pub fn divisors(n: u64) -> Divisors {
+    Divisors { n, i: 0, descending: false }
+}
+
+impl Iterator for Divisors {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while !self.descending {
+            self.i += 1;
+            if self.i.saturating_mul(self.i) > self.n {
+                self.descending = true;
+                break;
+            }
+            if self.n.is_multiple_of(self.i) {
+                return Some(self.i);
+            }
+        }
+        while self.i > 1 {
+            self.i -= 1;
+            // Don't repeat the square root of a square.
+            if self.n.is_multiple_of(self.i) && self.i * self.i != self.n {
+                return Some(self.n / self.i);
+            }
+        }
+        self.i = 0;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digit_counts() {
+        assert_eq!(num_digits(0, 10), 1);
+        assert_eq!(num_digits(9, 10), 1);
+        assert_eq!(num_digits(10, 10), 2);
+        assert_eq!(num_digits(u64::MAX, 10), 20);
+        assert_eq!(num_digits(255, 16), 2);
+        assert_eq!(num_digits(256, 2), 9);
+        assert_eq!(pow10(19), 10_000_000_000_000_000_000);
+        for n in (0..5000).chain([u64::MAX - 1, 99_999_999_999]) {
+            assert_eq!(num_digits(n, 10) as usize, n.to_string().len());
+        }
+    }
+
+    #[test]
+    fn digits_round_trip() {
+        assert_eq!(digits(1234, 10).collect::<Vec<_>>(), [1, 2, 3, 4]);
+        assert_eq!(digits(1234, 10).rev().collect::<Vec<_>>(), [4, 3, 2, 1]);
+        assert_eq!(digits(0, 10).collect::<Vec<_>>(), [0]);
+        assert_eq!(digits(6, 2).collect::<Vec<_>>(), [1, 1, 0]);
+        let mut both_ends = digits(12345, 10);
+        assert_eq!((both_ends.next(), both_ends.next_back(), both_ends.len()), (Some(1), Some(5), 3));
+        assert_eq!(both_ends.collect::<Vec<_>>(), [2, 3, 4]);
+        for base in [2, 3, 10, 16] {
+            for n in [0, 1, 7, 100, 65535, u64::MAX] {
+                assert_eq!(from_digits(digits(n, base), base), n);
+            }
+        }
+    }
+
+    #[test]
+    fn repeated_blocks() {
+        assert_eq!(repunit(1, 3), 111);
+        assert_eq!(repunit(3, 2), 1001);
+        assert!(is_repeated(1212, 2));
+        assert!(is_repeated(123123123, 3));
+        assert!(is_repeated(7, 1));
+        assert!(is_repeated(1010, 2));
+        assert!(!is_repeated(1213, 2));
+        assert!(!is_repeated(123123, 3));
+        assert!(!is_repeated(12312, 2));
+        assert!(!is_repeated(1010101, 2));
+    }
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(gcd(17, 5), 1);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        assert_eq!(modulo(-1, 100), 99);
+        assert_eq!(modulo(-200, 100), 0);
+        assert_eq!(modulo(250i64, 100), 50);
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(3, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(4, 8), None);
+        for a in 1..97 {
+            assert_eq!(a * mod_inverse(a, 97).unwrap() % 97, 1);
+        }
+    }
+
+    #[test]
+    fn divisors_in_order() {
+        assert_eq!(divisors(12).collect::<Vec<_>>(), [1, 2, 3, 4, 6, 12]);
+        assert_eq!(divisors(36).collect::<Vec<_>>(), [1, 2, 3, 4, 6, 9, 12, 18, 36]);
+        assert_eq!(divisors(1).collect::<Vec<_>>(), [1]);
+        assert_eq!(divisors(13).collect::<Vec<_>>(), [1, 13]);
+        assert_eq!(divisors(0).count(), 0);
+        let mut finished = divisors(4);
+        assert_eq!(finished.by_ref().count(), 3);
+        assert_eq!(finished.next(), None);
+        for n in 1..300u64 {
+            assert!(divisors(n).eq((1..=n).filter(|d| n.is_multiple_of(*d))));
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::answers::{Answers, ANSWERS_FILE};
+use crate::input::{example_number, find_day_dir, input_files, InputError, InputSource};
+use crate::runner::RunError;
+use crate::solution::{Answer, DynSolution, Part};
+use std::fs::read_to_string;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: Answer },
+    Missing
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub day: u32,
+    pub file: String,
+    pub part: Part,
+    pub answer: Answer,
+    pub status: Status,
+    pub elapsed: Duration
+}
+
+fn read(path: &Path) -> Result<String, InputError> {
+    read_to_string(path).map_err(|error| InputError::Read { source: InputSource::File(path.to_path_buf()), error })
+}
+
+fn load_answers(dir: &Path) -> Result<Answers, RunError> {
+    let path = dir.join(ANSWERS_FILE);
+    if !path.is_file() {
+        return Ok(Answers::default());
+    }
+    let text = read(&path)?;
+    Answers::parse(&text).map_err(|err| err.in_file(path.display().to_string()).into())
+}
+
+// Runs every part of the day against each of its input files and compares the
+// results with the recorded answers. Parts without a recorded answer are still
+// run so that their result can be recorded.
+pub fn verify_day(solution: &dyn DynSolution, parts: &[Part]) -> Result<Vec<Check>, RunError> {
+    let day = solution.day();
+    let dir = find_day_dir(day)?;
+    let answers = load_answers(&dir)?;
+    let mut files = input_files(&dir);
+    for file in answers.files() {
+        if !files.iter().any(|f| f == file) {
+            files.push(file.to_string());
+        }
+    }
+    let mut checks = Vec::new();
+    for file in files {
+        let path = dir.join(&file);
+        let input = read(&path)?;
+        let parsed = solution.parse_input(&input, example_number(&file).is_some_and(|n| n > 0)).map_err(|err| err.in_file(path.display().to_string()))?;
+        for &part in parts {
+            let start = Instant::now();
+            let answer = solution.solve(parsed.as_ref(), part);
+            let elapsed = start.elapsed();
+            let status = match answers.get(&file, part) {
+                Some(expected) if expected == answer => Status::Pass,
+                Some(expected) => Status::Fail { expected },
+                // Nothing to record for parts without a puzzle.
+                None if answer == Answer::Unavailable => continue,
+                None => Status::Missing
+            };
+            checks.push(Check { day, file: file.clone(), part, answer, status, elapsed });
+        }
+    }
+    Ok(checks)
+}
@@ -0,0 +1,211 @@
+use aoc2025::days::*;
+use aoc2025::differential::{check, with_char_replaced, with_smaller_numbers, without_each_line, Failure};
+use aoc2025::generator::{self, Rng};
+use aoc2025::solution::{Answer, Solution};
+use aoc2025::util::{Point2, RectilinearPolygon};
+use std::fmt::Debug;
+use std::ops::Range;
+
+fn assert_passes<T: Debug>(result: Result<(), Failure<T>>) {
+    if let Err(failure) = result {
+        panic!("seed {} failed, shrunk in {} steps to:\n{:#?}", failure.seed, failure.shrink_steps, failure.shrunk);
+    }
+}
+
+// Text cases are printed as is rather than as an escaped string.
+fn assert_text_passes(result: Result<(), Failure<String>>) {
+    if let Err(failure) = result {
+        panic!("seed {} failed, shrunk in {} steps to:\n{}", failure.seed, failure.shrink_steps, failure.shrunk);
+    }
+}
+
+#[test]
+fn day1_count_tmp_zeros() {
+    assert_text_passes(check(
+        500,
+        |rng| {
+            let size = rng.range(1, 30) as usize;
+            generator::dial_rotations(rng, size)
+        },
+        |text| [without_each_line(text), with_smaller_numbers(text, 0)].concat(),
+        |text| {
+            let Ok(rotations) = day1::Day1.parse(text) else { return false; };
+            day1::count_tmp_zeros(&rotations) != day1::count_tmp_zeros_by_clicking(&rotations)
+        }
+    ));
+}
+
+#[test]
+fn day7_count_timelines() {
+    assert_text_passes(check(
+        200,
+        |rng| {
+            let size = rng.range(1, 8) as usize;
+            generator::splitter_manifold(rng, size)
+        },
+        |text| [without_each_line(text), with_char_replaced(text, '^', '.')].concat(),
+        |text| {
+            let Ok(lines) = day7::Day7.parse(text) else { return false; };
+            day7::count_timelines(&lines) != day7::count_timelines_by_enumeration(&lines)
+        }
+    ));
+}
+
+#[test]
+fn day10_light_presses() {
+    assert_text_passes(check(
+        300,
+        |rng| {
+            let size = rng.range(1, 4) as usize;
+            generator::machines(rng, size)
+        },
+        |text| [without_each_line(text), with_char_replaced(text, '#', '.')].concat(),
+        |text| {
+            let Ok(problems) = day10::Day10.parse(text) else { return false; };
+            problems.iter().any(|problem| {
+                day10::solve_lights(problem).map(|presses| presses.count) != day10::solve_lights_by_search(problem)
+            })
+        }
+    ));
+}
+
+#[test]
+fn day10_joltage_presses() {
+    assert_text_passes(check(
+        100,
+        |rng| generator::machines(rng, 1),
+        |text| [without_each_line(text), with_smaller_numbers(text, 0)].concat(),
+        |text| {
+            let Ok(problems) = day10::Day10.parse(text) else { return false; };
+            problems.iter().any(|problem| day10::solve_joltage(problem).ok() != day10::solve_joltage_by_search(problem))
+        }
+    ));
+}
+
+// Polygons can't be shrunk by editing their text without breaking them, so
+// cases are a seed and a size and shrink by regenerating at smaller sizes.
+fn small_polygon(seed: u64, size: usize) -> RectilinearPolygon<u32> {
+    let vertices = generator::polygon_vertices(&mut Rng::new(seed), size, 4);
+    RectilinearPolygon::new(vertices.into_iter().map(|vertex| vertex.map(|c| c as u32)).collect()).unwrap()
+}
+
+fn smaller_sizes(&(seed, size): &(u64, usize)) -> Vec<(u64, usize)> {
+    (1..size).map(|size| (seed, size)).collect()
+}
+
+// Slow reference for the Day 9 polygon which records every tile on or inside
+// the boundary. Only suitable for small coordinates.
+struct Raster {
+    tiles: Vec<Vec<bool>>
+}
+
+impl Raster {
+    fn new(polygon: &RectilinearPolygon<u32>) -> Self {
+        let max = polygon.bounding_box().max;
+        let mut tiles = vec![vec![false; max.x as usize + 1]; max.y as usize + 1];
+        for (y, row) in tiles.iter_mut().enumerate() {
+            for (x, tile) in row.iter_mut().enumerate() {
+                let (x, y) = (x as u32, y as u32);
+                let on_boundary =
+                    polygon.horizontal_edges().any(|(edge_y, xs)| edge_y == y && xs.contains(&x)) ||
+                    polygon.vertical_edges().any(|(edge_x, ys)| edge_x == x && ys.contains(&y));
+                // Cast a ray to the right and count the vertical edges it
+                // crosses, counting an edge's lower end but not its upper one.
+                let crossings = polygon.vertical_edges().filter(|(edge_x, ys)| {
+                    *edge_x > x && *ys.start() <= y && y < *ys.end()
+                }).count();
+                *tile = on_boundary || crossings % 2 == 1;
+            }
+        }
+        Raster { tiles }
+    }
+
+    fn contains(&self, x: u32, y: u32) -> bool {
+        self.tiles.get(y as usize).and_then(|row| row.get(x as usize)).copied().unwrap_or(false)
+    }
+
+    fn row_is_inside(&self, y: u32, xs: Range<u32>) -> bool {
+        xs.into_iter().all(|x| self.contains(x, y))
+    }
+
+    fn column_is_inside(&self, x: u32, ys: Range<u32>) -> bool {
+        ys.into_iter().all(|y| self.contains(x, y))
+    }
+}
+
+// Slow reference for Day 9 part 2 which checks every tile of every rectangle
+// against the raster.
+fn max_inclosed_area_by_rasterising(polygon: &RectilinearPolygon<u32>) -> u64 {
+    let raster = Raster::new(polygon);
+    let coords = polygon.vertices();
+    let n = coords.len();
+    let pairs = (0..n).flat_map(|j| (0..j).map(move |i| (i, j)));
+    pairs.filter(|&(i, j)| {
+        let (a, b) = (coords[i], coords[j]);
+        let xs = a.x.min(b.x)..a.x.max(b.x) + 1;
+        (a.y.min(b.y)..=a.y.max(b.y)).all(|y| raster.row_is_inside(y, xs.clone()))
+    }).map(|(i, j)| (coords[i].x.abs_diff(coords[j].x) as u64 + 1) * (coords[i].y.abs_diff(coords[j].y) as u64 + 1)).max().unwrap()
+}
+
+#[test]
+fn day9_line_is_inside() {
+    assert_passes(check(
+        100,
+        |rng| (rng.next_u64(), rng.range(1, 10) as usize),
+        smaller_sizes,
+        |&(seed, size)| {
+            let coords = small_polygon(seed, size);
+            let raster = Raster::new(&coords);
+            let (horizonal_edges, vertical_edges) = day9::polygon_edges(&coords);
+            let Point2 { x: max_x, y: max_y } = coords.bounding_box().max;
+            let rows_differ = (0..=max_y).any(|y| (0..=max_x).any(|start| (start + 1..=max_x + 1).any(|end| {
+                day9::line_is_inside((y, start..end), &horizonal_edges, &vertical_edges) != raster.row_is_inside(y, start..end)
+            })));
+            let columns_differ = (0..=max_x).any(|x| (0..=max_y).any(|start| (start + 1..=max_y + 1).any(|end| {
+                day9::line_is_inside((x, start..end), &vertical_edges, &horizonal_edges) != raster.column_is_inside(x, start..end)
+            })));
+            rows_differ || columns_differ
+        }
+    ));
+}
+
+#[test]
+fn day9_max_inclosed_area() {
+    assert_passes(check(
+        100,
+        |rng| (rng.next_u64(), rng.range(1, 10) as usize),
+        smaller_sizes,
+        |&(seed, size)| {
+            let coords = small_polygon(seed, size);
+            day9::Day9.part2(&coords) != Answer::from(max_inclosed_area_by_rasterising(&coords))
+        }
+    ));
+}
+
+// The generator's presents with a few small regions, so that brute force
+// placement stays quick.
+fn small_packing(rng: &mut Rng) -> String {
+    let mut text = generator::shape_packing(rng, 0);
+    for _ in 0..rng.range(1, 4) {
+        let mut counts = [0; 6];
+        for _ in 0..rng.range(1, 4) {
+            counts[rng.index(6)] += 1;
+        }
+        let counts: Vec<String> = counts.iter().map(u64::to_string).collect();
+        text += &format!("{}x{}: {}\n", rng.range(2, 6), rng.range(2, 6), counts.join(" "));
+    }
+    text
+}
+
+#[test]
+fn day12_packing() {
+    assert_text_passes(check(
+        200,
+        small_packing,
+        |text| [without_each_line(text), with_smaller_numbers(text, 1)].concat(),
+        |text| {
+            let Ok(input) = day12::Day12.parse(text) else { return false; };
+            day12::Day12.part1(&input) != Answer::from(day12::count_fitting_by_brute_force(&input))
+        }
+    ));
+}
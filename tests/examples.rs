@@ -0,0 +1,107 @@
+use aoc2025::days::*;
+use aoc2025::input::example_file_name;
+use aoc2025::solution::{Answer, DynSolution, Part};
+use std::fs::read_to_string;
+use std::path::Path;
+
+fn run_example(solution: &dyn DynSolution, example: u32, part: Part) -> Answer {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("inputs")
+        .join(solution.day().to_string())
+        .join(example_file_name(example));
+    let input = read_to_string(&path).unwrap();
+    solution.run_example(&input, part).unwrap()
+}
+
+fn check(solution: &dyn DynSolution, part1: u64, part2: u64) {
+    assert_eq!(run_example(solution, 1, Part::One), Answer::Number(part1));
+    assert_eq!(run_example(solution, 1, Part::Two), Answer::Number(part2));
+}
+
+#[test]
+fn day1() {
+    check(&day1::Day1, 3, 6);
+}
+
+#[test]
+fn day2() {
+    check(&day2::Day2, 1227775554, 4174379265);
+}
+
+#[test]
+fn day3() {
+    check(&day3::Day3, 357, 3121910778619);
+}
+
+#[test]
+fn day4() {
+    check(&day4::Day4, 13, 43);
+}
+
+#[test]
+fn day5() {
+    check(&day5::Day5, 3, 14);
+}
+
+#[test]
+fn day6() {
+    check(&day6::Day6, 4277556, 3263827);
+}
+
+#[test]
+fn day7() {
+    check(&day7::Day7, 21, 40);
+}
+
+#[test]
+fn day8() {
+    check(&day8::Day8, 40, 25272);
+}
+
+#[test]
+fn day9() {
+    check(&day9::Day9, 50, 24);
+}
+
+#[test]
+fn day10() {
+    check(&day10::Day10, 7, 33);
+}
+
+#[test]
+fn day11() {
+    // The second part has its own example.
+    assert_eq!(run_example(&day11::Day11, 1, Part::One), Answer::Number(5));
+    assert_eq!(run_example(&day11::Day11, 2, Part::Two), Answer::Number(2));
+}
+
+#[test]
+fn day12() {
+    assert_eq!(run_example(&day12::Day12, 1, Part::One), Answer::Number(2));
+    assert_eq!(run_example(&day12::Day12, 1, Part::Two), Answer::Unavailable);
+}
+
+#[test]
+fn inputs_too_small_to_solve_are_reported() {
+    let short = "987654321111111\n12345\n";
+    assert_eq!(day3::Day3.run(short, Part::One).unwrap(), Answer::Number(98 + 45));
+    assert_eq!(day3::Day3.run(short, Part::Two).unwrap(), Answer::Unsolvable("bank 2 has fewer than 12 batteries".to_string()));
+    let error = day6::Day6.run("1 99999999999999999999\n* +\n", Part::One).unwrap_err();
+    assert_eq!((error.line, error.column, error.expected.as_str()), (1, 3, "a number below 2^64"));
+    let error = day8::Day8.run("1,2,3\n4,5,6\n", Part::Two).unwrap_err();
+    assert_eq!((error.line, error.expected.as_str()), (3, "at least 3 junction boxes"));
+}
+
+#[test]
+fn day6_zero_digits() {
+    // The second column reads as 0 rather than separating two problems.
+    let input = "10 3\n20 4\n+  *\n";
+    assert_eq!(day6::Day6.run(input, Part::One).unwrap(), Answer::Number(30 + 3 * 4));
+    assert_eq!(day6::Day6.run(input, Part::Two).unwrap(), Answer::Number(12 + 34));
+}
+
+#[test]
+fn every_day_is_registered() {
+    let days: Vec<u32> = ALL.iter().map(|solution| solution.day()).collect();
+    assert_eq!(days, (1..=12).collect::<Vec<u32>>());
+}